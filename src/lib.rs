@@ -1,10 +1,24 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 
+use std::any::{type_name, TypeId};
+use std::collections::BTreeMap;
+use std::error::Error;
 use std::marker::PhantomData;
 
+use bevy_app::App;
+use bevy_ecs::bundle::BundleFromComponents;
+use bevy_ecs::entity::{EntityHashMap, EntityHashSet};
 use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_log::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_reflect::FromType;
+use bevy_scene::{DynamicEntity, DynamicScene};
 use moonshine_util::Static;
+use ron::value::RawValue;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Types, traits, and functions related to loading.
 pub mod load;
@@ -15,13 +29,20 @@ pub mod save;
 /// Common elements for saving/loading world state.
 pub mod prelude {
     pub use crate::load::{
-        load_on, load_on_default_event, LoadError, LoadEvent, LoadInput, LoadWorld, Loaded,
-        TriggerLoad, Unload,
+        load_on, load_on_default_event, DefaultUnloadFilter, KeepOnLoad, LoadError, LoadEvent,
+        LoadInput, LoadMode, LoadWorld, Loaded, MergeBy, MissingResourcePolicy, TriggerLoad,
+        Unload, UnloadPolicy,
     };
 
     pub use crate::save::{
-        save_on, save_on_default_event, Save, SaveError, SaveEvent, SaveOutput, SaveWorld, Saved,
-        TriggerSave,
+        save_on, save_on_default_event, save_readonly, DefaultSaveFilter, DontSave,
+        RegisterTransient, Save, SaveError, SaveEvent, SaveOutput, SavePrune, SaveSkip, SaveWorld,
+        Saved, TriggerSave,
+    };
+
+    pub use crate::{
+        BundleInput, MapBundle, MapComponentWithContext, MapContext, MapError, MapResource,
+        ReflectSaveHooks, RegisterSaveMapping, RegisterSerdeComponent, SaveHooks, TryMapComponent,
     };
 
     pub use bevy_ecs::{
@@ -40,7 +61,15 @@ pub mod prelude {
 /// When invoked, the given component `T` will be replaced with the output of the mapper for all saved entities.
 /// When the save operation is complete, the original component will be restored.
 ///
+/// If a saved entity already has a component of the output type, it is preserved and restored
+/// when the save operation is complete. Similarly, if a loaded entity already has a component of
+/// the output type, it is replaced by the mapped output. Both cases are reported as a warning.
+///
 /// Keep in mind that this will trigger [change detection](DetectChanges) for the mapped component.
+///
+/// Alternatively, if the mapped output is [`Reflect`], the mapper may be applied directly to the
+/// saved [`DynamicScene`] using [`SceneMapper::map_in_scene`]. In this case, the world is never modified,
+/// and no component hooks or observers are triggered for the mapped output.
 pub trait MapComponent<T: Component>: 'static + Clone + Send + Sync {
     /// The mapped output type.
     type Output: Component;
@@ -60,68 +89,1160 @@ where
     }
 }
 
-/// A collection of component mappers. See [`MapComponent`] for more information.
+/// Context of a component being mapped by a [`MapComponentWithContext`] mapper.
+#[derive(Clone, Copy)]
+pub struct MapContext<'a> {
+    /// The [`Entity`] which owns the mapped component.
+    pub entity: Entity,
+    /// Read access to the [`World`] which contains the mapped entity.
+    pub world: &'a World,
+}
+
+/// A variant of [`MapComponent`] which also receives the [`MapContext`] of the mapped component.
+///
+/// This is useful if the mapping depends on other components or resources, such as mapping asset
+/// handles into asset paths, or entity references into names.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Target(Entity);
+///
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct TargetName(String);
+///
+/// fn save(mut commands: Commands) {
+///     commands.trigger_save(
+///         SaveWorld::default_into_file("world.ron").map_component_with_context(
+///             |Target(target): &Target, context: MapContext| {
+///                 let name = context.world.get::<Name>(*target);
+///                 TargetName(name.map(Name::to_string).unwrap_or_default())
+///             },
+///         ),
+///     );
+/// }
+/// ```
+pub trait MapComponentWithContext<T: Component>: 'static + Clone + Send + Sync {
+    /// The mapped output type.
+    type Output: Component;
+
+    /// Called during the Save/Load process to map components.
+    fn map_component(&self, component: &T, context: MapContext) -> Self::Output;
+}
+
+impl<F: Fn(&T, MapContext) -> U, T: Component, U: Component> MapComponentWithContext<T> for F
+where
+    F: 'static + Clone + Send + Sync,
+{
+    type Output = U;
+
+    fn map_component(&self, component: &T, context: MapContext) -> Self::Output {
+        self(component, context)
+    }
+}
+
+/// A fallible variant of [`MapComponent`].
+///
+/// The mapper may return an [`Option`] to drop the component, or a [`Result`] to abort the
+/// save or load process with an error.
+///
+/// When a component is dropped during save, it is not saved. When a component is dropped during load,
+/// it is removed from the loaded entity.
+///
+/// If an error is returned during save, the save process is aborted with [`SaveError::Map`](save::SaveError::Map).
+/// If an error is returned during load, the load process returns [`LoadError::Map`](load::LoadError::Map).
+/// Note that the loaded world is not reverted in this case.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Cooldown(Option<Timer>);
+///
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct SavedCooldown(f32);
+///
+/// fn save(mut commands: Commands) {
+///     commands.trigger_save(SaveWorld::default_into_file("world.ron").try_map_component(
+///         |Cooldown(timer): &Cooldown| timer.as_ref().map(|timer| SavedCooldown(timer.remaining_secs())),
+///     ));
+/// }
+/// ```
+pub trait TryMapComponent<T: Component>: 'static + Clone + Send + Sync {
+    /// The mapped output type.
+    type Output: Component;
+
+    /// Called during the Save/Load process to map components.
+    ///
+    /// Returns `Ok(None)` if the component should be dropped.
+    fn try_map_component(&self, component: &T) -> Result<Option<Self::Output>, MapError>;
+}
+
+impl<F: Fn(&T) -> O, T: Component, O: TryMapOutput> TryMapComponent<T> for F
+where
+    F: 'static + Clone + Send + Sync,
+{
+    type Output = O::Output;
+
+    fn try_map_component(&self, component: &T) -> Result<Option<Self::Output>, MapError> {
+        self(component).into_result()
+    }
+}
+
+/// An error returned by a [fallible component mapper](TryMapComponent).
+pub type MapError = Box<dyn Error + Send + Sync>;
+
+/// Output of a [fallible component mapper](TryMapComponent).
+///
+/// This is implemented for [`Option`] and [`Result`] of any [`Component`].
+pub trait TryMapOutput {
+    /// The mapped output type.
+    type Output: Component;
+
+    /// Converts this output into a result of an optional mapped component.
+    fn into_result(self) -> Result<Option<Self::Output>, MapError>;
+}
+
+impl<U: Component> TryMapOutput for Option<U> {
+    type Output = U;
+
+    fn into_result(self) -> Result<Option<U>, MapError> {
+        Ok(self)
+    }
+}
+
+impl<U: Component, E: Error + Send + Sync + 'static> TryMapOutput for Result<U, E> {
+    type Output = U;
+
+    fn into_result(self) -> Result<Option<U>, MapError> {
+        self.map(Some).map_err(Into::into)
+    }
+}
+
+/// Common interface of all component mappers.
+trait MapComponentDyn<T: Component>: Static {
+    type Output: Component;
+
+    fn try_map(&self, component: &T, context: MapContext)
+        -> Result<Option<Self::Output>, MapError>;
+}
+
+/// Adapts a [`MapComponent`] into a [`MapComponentDyn`].
+struct Simple<M>(M);
+
+impl<T: Component, M: MapComponent<T>> MapComponentDyn<T> for Simple<M> {
+    type Output = M::Output;
+
+    fn try_map(&self, component: &T, _context: MapContext) -> Result<Option<M::Output>, MapError> {
+        Ok(Some(self.0.map_component(component)))
+    }
+}
+
+/// Adapts a [`MapComponentWithContext`] into a [`MapComponentDyn`].
+struct WithContext<M>(M);
+
+impl<T: Component, M: MapComponentWithContext<T>> MapComponentDyn<T> for WithContext<M> {
+    type Output = M::Output;
+
+    fn try_map(&self, component: &T, context: MapContext) -> Result<Option<M::Output>, MapError> {
+        Ok(Some(self.0.map_component(component, context)))
+    }
+}
+
+/// Adapts a [`TryMapComponent`] into a [`MapComponentDyn`].
+struct Fallible<M>(M);
+
+impl<T: Component, M: TryMapComponent<T>> MapComponentDyn<T> for Fallible<M> {
+    type Output = M::Output;
+
+    fn try_map(&self, component: &T, _context: MapContext) -> Result<Option<M::Output>, MapError> {
+        self.0.try_map_component(component)
+    }
+}
+
+/// A trait used for mapping resources during a save operation.
+///
+/// This is the [`Resource`] equivalent of [`MapComponent`].
+///
+/// During save, the output of the mapper is inserted into the world and saved instead of
+/// the resource `R`. When the save operation is complete, it is removed again.
+/// During load, the loaded resource `R` is replaced with the output of the mapper.
+pub trait MapResource<R: Resource>: 'static + Clone + Send + Sync {
+    /// The mapped output type.
+    type Output: Resource;
+
+    /// Called during the Save/Load process to map resources.
+    fn map_resource(&self, resource: &R) -> Self::Output;
+}
+
+impl<F: Fn(&R) -> U, R: Resource, U: Resource> MapResource<R> for F
+where
+    F: 'static + Clone + Send + Sync,
+{
+    type Output = U;
+
+    fn map_resource(&self, resource: &R) -> Self::Output {
+        self(resource)
+    }
+}
+
+/// A trait used for mapping several components of an entity into a different [`Bundle`] during a save operation.
+///
+/// This is useful if a set of components is saved as a single serializable component.
+/// During save, the mapped output is inserted into each saved entity which has all components of `B`,
+/// and removed when the save operation is complete. During load, the loaded components of `B` are
+/// replaced with the mapped output.
+///
+/// If a saved entity already has all components of the output, they are preserved and restored
+/// when the save operation is complete. If it only has some of them, the entity is not mapped.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component, Clone)]
+/// struct Velocity(Vec3);
+///
+/// #[derive(Component, Clone, Reflect)]
+/// #[reflect(Component)]
+/// struct PhysicsSave {
+///     translation: Vec3,
+///     velocity: Vec3,
+/// }
+///
+/// fn save(mut commands: Commands) {
+///     commands.trigger_save(
+///         SaveWorld::default_into_file("world.ron")
+///             .exclude_component::<Transform>()
+///             .map_bundle(|(transform, velocity): &(Transform, Velocity)| PhysicsSave {
+///                 translation: transform.translation,
+///                 velocity: velocity.0,
+///             }),
+///     );
+/// }
+///
+/// fn load(mut commands: Commands) {
+///     commands.trigger_load(LoadWorld::default_from_file("world.ron").map_bundle(
+///         |(physics,): &(PhysicsSave,)| {
+///             (Transform::from_translation(physics.translation), Velocity(physics.velocity))
+///         },
+///     ));
+/// }
+/// ```
+pub trait MapBundle<B: BundleInput>: 'static + Clone + Send + Sync {
+    /// The mapped output type.
+    type Output: Bundle + BundleFromComponents;
+
+    /// Called during the Save/Load process to map bundles.
+    fn map_bundle(&self, bundle: &B) -> Self::Output;
+}
+
+impl<F: Fn(&B) -> U, B: BundleInput, U: Bundle + BundleFromComponents> MapBundle<B> for F
+where
+    F: 'static + Clone + Send + Sync,
+{
+    type Output = U;
+
+    fn map_bundle(&self, bundle: &B) -> Self::Output {
+        self(bundle)
+    }
+}
+
+/// A tuple of [`Clone`] components which may be used as the input of a [`MapBundle`].
+pub trait BundleInput: Bundle + BundleFromComponents {
+    /// Returns a copy of all components of this bundle from the given entity,
+    /// or `None` if any of them is missing.
+    fn from_entity(entity: EntityRef) -> Option<Self>;
+}
+
+macro_rules! impl_bundle_input {
+    ($($T:ident),*) => {
+        impl<$($T: Component + Clone),*> BundleInput for ($($T,)*) {
+            fn from_entity(entity: EntityRef) -> Option<Self> {
+                Some(($(entity.get::<$T>()?.clone(),)*))
+            }
+        }
+    };
+}
+
+impl_bundle_input!(A);
+impl_bundle_input!(A, B);
+impl_bundle_input!(A, B, C);
+impl_bundle_input!(A, B, C, D);
+impl_bundle_input!(A, B, C, D, E);
+impl_bundle_input!(A, B, C, D, E, F);
+impl_bundle_input!(A, B, C, D, E, F, G);
+impl_bundle_input!(A, B, C, D, E, F, G, H);
+
+/// An extension trait for [`App`] used to register component mappings for all save and load operations.
+///
+/// A save mapping is a pair of [component mappers](MapComponent) which is applied by every [`SaveWorld`](save::SaveWorld)
+/// during save, and in reverse by every [`LoadWorld`](load::LoadWorld) during load.
+///
+/// Registered mappers are applied before any mappers of the [`SaveWorld`](save::SaveWorld) during save,
+/// and after any mappers of the [`LoadWorld`](load::LoadWorld) during load, in reverse order of registration.
+/// This allows a load mapper to convert legacy saved data into the input of a registered mapping.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Health(Box<u32>); // Not serializable
+///
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct SavedHealth(u32);
+///
+/// let mut app = App::new();
+/// app.register_save_mapping(
+///     |Health(value): &Health| SavedHealth(**value),
+///     |&SavedHealth(value): &SavedHealth| Health(Box::new(value)),
+/// );
+/// ```
+pub trait RegisterSaveMapping {
+    /// Maps `T` into `U` before every save, and `U` back into `T` after every load.
+    fn register_save_mapping<T: Component, U: Component>(
+        &mut self,
+        to: impl MapComponent<T, Output = U>,
+        from: impl MapComponent<U, Output = T>,
+    ) -> &mut Self;
+}
+
+impl RegisterSaveMapping for App {
+    fn register_save_mapping<T: Component, U: Component>(
+        &mut self,
+        to: impl MapComponent<T, Output = U>,
+        from: impl MapComponent<U, Output = T>,
+    ) -> &mut Self {
+        let mut mappings = self.world_mut().get_resource_or_init::<SaveMappings>();
+        mappings.save.push(Box::new(move || {
+            Box::new(ComponentMapperImpl::new(Simple(to.clone())))
+        }));
+        mappings.load.push(Box::new(move || {
+            Box::new(ComponentMapperImpl::new(Simple(from.clone())))
+        }));
+        self
+    }
+}
+
+/// An extension trait for [`App`] used to save and load components which implement [`Serialize`]
+/// and [`DeserializeOwned`], but not [`Reflect`].
+///
+/// These components are saved into [`SerdeComponents`] alongside the reflected scene data.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use bevy::reflect::TypePath;
+/// use moonshine_save::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Component, TypePath, Serialize, Deserialize)]
+/// struct Inventory(Vec<String>); // Not reflected
+///
+/// let mut app = App::new();
+/// app.register_serde_component::<Inventory>();
+/// ```
+pub trait RegisterSerdeComponent {
+    /// Saves and loads `T` using [`serde`] instead of [`Reflect`].
+    ///
+    /// The saved data is keyed by the [`TypePath`] of `T`, so it must remain stable between saves and loads.
+    /// Note that entity references within `T` are not mapped during load.
+    ///
+    /// Serde components are saved by modifying the world. Once any serde component is registered,
+    /// [`save_readonly`](save::save_readonly) always fails with [`SaveError::ReadOnly`](save::SaveError::ReadOnly).
+    fn register_serde_component<T: Component + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+    ) -> &mut Self;
+}
+
+impl RegisterSerdeComponent for App {
+    fn register_serde_component<T: Component + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+    ) -> &mut Self {
+        self.register_type::<SerdeComponents>();
+        let mut mappings = self.world_mut().get_resource_or_init::<SaveMappings>();
+        mappings
+            .save
+            .push(Box::new(|| Box::new(SerdeComponentMapper::<T>::default())));
+        mappings
+            .load
+            .push(Box::new(|| Box::new(SerdeComponentMapper::<T>::default())));
+        self
+    }
+}
+
+/// A component which stores the serialized data of all components registered using
+/// [`register_serde_component`](RegisterSerdeComponent::register_serde_component), keyed by [`TypePath`].
+///
+/// This component is inserted into saved entities during save, and removed from loaded entities after load.
+/// Entries of components which are not registered are kept in this component when loaded, and saved again.
+#[derive(Component, Reflect, Default, Clone, Debug)]
+#[reflect(opaque)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct SerdeComponents(pub BTreeMap<String, Box<RawValue>>);
+
+impl Serialize for SerdeComponents {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SerdeComponents {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        BTreeMap::deserialize(deserializer).map(Self)
+    }
+}
+
+/// The [`SerdeComponents`] of a saved entity before any serde components were saved into it.
+#[derive(Component)]
+struct SerdeComponentsSnapshot(Option<SerdeComponents>);
+
+/// Component mapper for components registered using [`RegisterSerdeComponent::register_serde_component`].
+struct SerdeComponentMapper<T> {
+    applied: EntityHashSet,
+    marker: PhantomData<T>,
+}
+
+impl<T> Default for SerdeComponentMapper<T> {
+    fn default() -> Self {
+        Self {
+            applied: EntityHashSet::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Component + TypePath + Serialize + DeserializeOwned> ComponentMapper
+    for SerdeComponentMapper<T>
+{
+    fn apply(&mut self, entity: &mut EntityWorldMut) -> Result<(), MapError> {
+        let Some(component) = entity.get::<T>() else {
+            return Ok(());
+        };
+
+        let data = RawValue::from_rust(component)?;
+        if !entity.contains::<SerdeComponentsSnapshot>() {
+            let existing = entity.get::<SerdeComponents>().cloned();
+            entity.insert(SerdeComponentsSnapshot(existing));
+        }
+        entity
+            .entry::<SerdeComponents>()
+            .or_default()
+            .get_mut()
+            .0
+            .insert(T::type_path().to_string(), data);
+        self.applied.insert(entity.id());
+        Ok(())
+    }
+
+    fn replace(&mut self, entity: &mut EntityWorldMut) -> Result<(), MapError> {
+        let Some(mut serde_components) = entity.get_mut::<SerdeComponents>() else {
+            return Ok(());
+        };
+
+        let Some(data) = serde_components.0.remove(T::type_path()) else {
+            return Ok(());
+        };
+
+        if serde_components.0.is_empty() {
+            entity.remove::<SerdeComponents>();
+        }
+
+        let component: T = data.into_rust()?;
+        entity.insert(component);
+        Ok(())
+    }
+
+    fn undo(&mut self, world: &mut World) {
+        for entity in self.applied.drain() {
+            let Ok(mut entity) = world.get_entity_mut(entity) else {
+                continue;
+            };
+
+            // The snapshot is shared by all serde component mappers, so only the first one restores it
+            let Some(SerdeComponentsSnapshot(existing)) = entity.take::<SerdeComponentsSnapshot>()
+            else {
+                continue;
+            };
+
+            match existing {
+                Some(existing) => entity.insert(existing),
+                None => entity.remove::<SerdeComponents>(),
+            };
+        }
+    }
+}
+
+/// A trait for components which need to fix up their own state before save or after load.
+///
+/// To enable the hooks, register [`ReflectSaveHooks`] for the component using `#[reflect(SaveHooks)]`.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component, SaveHooks)]
+/// struct Path {
+///     points: Vec<Vec2>,
+///     #[reflect(ignore)]
+///     length: f32, // Derived from points
+/// }
+///
+/// impl SaveHooks for Path {
+///     fn after_load(entity: &mut EntityWorldMut) {
+///         let mut path = entity.get_mut::<Path>().unwrap();
+///         path.length = path.points.windows(2).map(|p| p[0].distance(p[1])).sum();
+///     }
+/// }
+/// ```
+pub trait SaveHooks: Component {
+    /// Called on each saved entity with this component before it is extracted into the saved scene.
+    ///
+    /// [`save_readonly`](save::save_readonly) fails if any saved entity has a component with save hooks.
+    fn prepare_for_save(_entity: &mut EntityWorldMut) {}
+
+    /// Called on each loaded entity with this component once the whole scene is spawned.
+    ///
+    /// This is called after [`LoadEvent::after_load`](load::LoadEvent::after_load), so all loaded components
+    /// are already unmapped.
+    fn after_load(_entity: &mut EntityWorldMut) {}
+}
+
+/// Type data for components which implement [`SaveHooks`].
+#[derive(Clone)]
+pub struct ReflectSaveHooks {
+    prepare_for_save: fn(&mut EntityWorldMut),
+    after_load: fn(&mut EntityWorldMut),
+}
+
+impl ReflectSaveHooks {
+    /// Calls [`SaveHooks::prepare_for_save`] on the given entity.
+    pub fn prepare_for_save(&self, entity: &mut EntityWorldMut) {
+        (self.prepare_for_save)(entity);
+    }
+
+    /// Calls [`SaveHooks::after_load`] on the given entity.
+    pub fn after_load(&self, entity: &mut EntityWorldMut) {
+        (self.after_load)(entity);
+    }
+}
+
+impl<T: SaveHooks> FromType<T> for ReflectSaveHooks {
+    fn from_type() -> Self {
+        Self {
+            prepare_for_save: T::prepare_for_save,
+            after_load: T::after_load,
+        }
+    }
+}
+
+/// Invokes the given [`ReflectSaveHooks`] function for all components of the given entities.
+fn run_save_hooks(
+    world: &mut World,
+    entities: impl IntoIterator<Item = Entity>,
+    hook: fn(&ReflectSaveHooks, &mut EntityWorldMut),
+) {
+    let Some(type_registry) = world.get_resource::<AppTypeRegistry>() else {
+        return;
+    };
+
+    let type_registry = type_registry.read();
+    let mut hooks = Vec::new();
+    for entity in entities {
+        let Ok(components) = world.inspect_entity(entity) else {
+            continue;
+        };
+        for component in components {
+            let Some(type_id) = component.type_id() else {
+                continue;
+            };
+            if let Some(save_hooks) = type_registry.get_type_data::<ReflectSaveHooks>(type_id) {
+                hooks.push((entity, save_hooks.clone()));
+            }
+        }
+    }
+    drop(type_registry);
+
+    for (entity, save_hooks) in hooks {
+        let Ok(mut entity) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        hook(&save_hooks, &mut entity);
+    }
+}
+
+/// Calls [`SaveHooks::prepare_for_save`] for all components of the given saved entities.
+pub(crate) fn prepare_for_save(world: &mut World, entities: &[Entity]) {
+    run_save_hooks(
+        world,
+        entities.iter().copied(),
+        ReflectSaveHooks::prepare_for_save,
+    );
+}
+
+/// Returns `true` if any of the given entities has a component with [`ReflectSaveHooks`].
+pub(crate) fn has_save_hooks(world: &World, entities: &[Entity]) -> bool {
+    let Some(type_registry) = world.get_resource::<AppTypeRegistry>() else {
+        return false;
+    };
+
+    let type_registry = type_registry.read();
+    entities
+        .iter()
+        .filter_map(|entity| world.inspect_entity(*entity).ok())
+        .flatten()
+        .filter_map(|component| component.type_id())
+        .any(|type_id| {
+            type_registry
+                .get_type_data::<ReflectSaveHooks>(type_id)
+                .is_some()
+        })
+}
+
+/// Calls [`SaveHooks::after_load`] for all components of the given loaded entities.
+pub(crate) fn after_load(world: &mut World, entities: impl IntoIterator<Item = Entity>) {
+    run_save_hooks(world, entities, ReflectSaveHooks::after_load);
+}
+
+/// Component mappers registered using [`RegisterSaveMapping`].
+#[derive(Resource, Default)]
+struct SaveMappings {
+    save: Vec<ComponentMapperFactory>,
+    load: Vec<ComponentMapperFactory>,
+}
+
+type ComponentMapperFactory = Box<dyn Fn() -> ComponentMapperDyn + Send + Sync>;
+
+/// Returns `true` if any save mappings are registered using [`RegisterSaveMapping`] or [`RegisterSerdeComponent`].
+pub(crate) fn has_save_mappings(world: &World) -> bool {
+    world
+        .get_resource::<SaveMappings>()
+        .is_some_and(|mappings| !mappings.save.is_empty())
+}
+
+/// A collection of component and resource mappers. See [`MapComponent`] and [`MapResource`] for more information.
 #[derive(Default)]
-pub struct SceneMapper(Vec<ComponentMapperDyn>);
+pub struct SceneMapper {
+    components: Vec<ComponentMapperDyn>,
+    scene: Vec<SceneComponentMapperDyn>,
+    resources: Vec<ResourceMapperDyn>,
+    error: Option<MapError>,
+}
 
 impl SceneMapper {
     /// Adds a component mapper to the scene mapper.
     pub fn map<T: Component>(mut self, m: impl MapComponent<T>) -> Self {
-        self.0.push(Box::new(ComponentMapperImpl::new(m)));
+        self.components
+            .push(Box::new(ComponentMapperImpl::new(Simple(m))));
+        self
+    }
+
+    /// Adds a component mapper with context to the scene mapper.
+    ///
+    /// See [`MapComponentWithContext`] for more information.
+    pub fn map_with_context<T: Component>(mut self, m: impl MapComponentWithContext<T>) -> Self {
+        self.components
+            .push(Box::new(ComponentMapperImpl::new(WithContext(m))));
         self
     }
 
+    /// Adds a fallible component mapper to the scene mapper.
+    ///
+    /// See [`TryMapComponent`] for more information.
+    pub fn try_map<T: Component>(mut self, m: impl TryMapComponent<T>) -> Self {
+        self.components
+            .push(Box::new(ComponentMapperImpl::new(Fallible(m))));
+        self
+    }
+
+    /// Adds a bundle mapper to the scene mapper.
+    pub fn map_bundle<B: BundleInput>(mut self, m: impl MapBundle<B>) -> Self {
+        self.components.push(Box::new(BundleMapperImpl::new(m)));
+        self
+    }
+
+    /// Adds a resource mapper to the scene mapper.
+    pub fn map_resource<R: Resource>(mut self, m: impl MapResource<R>) -> Self {
+        self.resources.push(Box::new(ResourceMapperImpl::new(m)));
+        self
+    }
+
+    /// Adds a component mapper which is applied to the saved [`DynamicScene`] instead of the world.
+    ///
+    /// The mapped output replaces the component `T` in the saved data of each saved entity.
+    /// If the saved data already contains the output component, it is replaced and a warning is reported.
+    /// Unlike [`SceneMapper::map`], this never modifies the world.
+    ///
+    /// This mapper is only used while saving.
+    pub fn map_in_scene<T: Component, M: MapComponent<T>>(mut self, m: M) -> Self
+    where
+        M::Output: Reflect,
+    {
+        self.scene
+            .push(Box::new(ComponentMapperImpl::new(Simple(m))));
+        self
+    }
+
+    /// Returns `true` if any mapper of this scene mapper modifies the world.
+    ///
+    /// Only mappers added using [`SceneMapper::map_in_scene`] never modify the world.
+    pub(crate) fn modifies_world(&self) -> bool {
+        !self.components.is_empty() || !self.resources.is_empty()
+    }
+
     pub(crate) fn apply(&mut self, mut entity: EntityWorldMut) {
-        for mapper in &mut self.0 {
-            mapper.apply(&mut entity);
+        if self.error.is_some() {
+            return;
+        }
+
+        for mapper in &mut self.components {
+            if let Err(error) = mapper.apply(&mut entity) {
+                self.error = Some(error);
+                return;
+            }
+        }
+    }
+
+    pub(crate) fn apply_scene(&mut self, world: &World, scene: &mut DynamicScene) {
+        for scene_entity in &mut scene.entities {
+            for mapper in &mut self.components {
+                mapper.remove_dropped(scene_entity);
+            }
+
+            for mapper in &self.scene {
+                mapper.apply_scene(world, scene_entity);
+            }
         }
     }
 
     pub(crate) fn replace(&mut self, mut entity: EntityWorldMut) {
-        for mapper in &mut self.0 {
-            mapper.replace(&mut entity);
+        if self.error.is_some() {
+            return;
+        }
+
+        for mapper in &mut self.components {
+            if let Err(error) = mapper.replace(&mut entity) {
+                self.error = Some(error);
+                return;
+            }
+        }
+
+        if let Some(SerdeComponents(unregistered)) = entity.get::<SerdeComponents>() {
+            warn!(
+                "{:?} has saved data of unregistered serde components {:?}; it will be kept in {}",
+                entity.id(),
+                unregistered.keys().collect::<Vec<_>>(),
+                type_name::<SerdeComponents>()
+            );
+        }
+    }
+
+    pub(crate) fn undo(&mut self, world: &mut World) {
+        for mapper in &mut self.components {
+            mapper.undo(world);
+        }
+    }
+
+    pub(crate) fn apply_resources(&mut self, world: &mut World) {
+        for mapper in &mut self.resources {
+            mapper.apply(world);
+        }
+    }
+
+    pub(crate) fn replace_resources(&mut self, world: &mut World) {
+        for mapper in &mut self.resources {
+            mapper.replace(world);
+        }
+    }
+
+    pub(crate) fn undo_resources(&mut self, world: &mut World) {
+        for mapper in &mut self.resources {
+            mapper.undo(world);
         }
     }
 
-    pub(crate) fn undo(&mut self, mut entity: EntityWorldMut) {
-        for mapper in &mut self.0 {
-            mapper.undo(&mut entity);
+    /// Adds all save mappings registered using [`RegisterSaveMapping`] to this mapper.
+    pub(crate) fn add_save_mappings(&mut self, world: &World) {
+        if let Some(mappings) = world.get_resource::<SaveMappings>() {
+            let registered = mappings.save.iter().map(|make| make());
+            self.components.splice(0..0, registered);
         }
     }
+
+    /// Adds the reverse of all save mappings registered using [`RegisterSaveMapping`] to this mapper.
+    ///
+    /// Registered mappers are applied after all other mappers, in reverse order of registration.
+    pub(crate) fn add_load_mappings(&mut self, world: &World) {
+        if let Some(mappings) = world.get_resource::<SaveMappings>() {
+            let registered = mappings.load.iter().rev().map(|make| make());
+            self.components.extend(registered);
+        }
+    }
+
+    /// Returns the first error returned by a fallible mapper, if any.
+    pub(crate) fn take_error(&mut self) -> Option<MapError> {
+        self.error.take()
+    }
 }
 
 trait ComponentMapper: Static {
-    fn apply(&mut self, entity: &mut EntityWorldMut);
+    fn apply(&mut self, entity: &mut EntityWorldMut) -> Result<(), MapError>;
+
+    fn replace(&mut self, entity: &mut EntityWorldMut) -> Result<(), MapError>;
 
-    fn replace(&mut self, entity: &mut EntityWorldMut);
+    fn undo(&mut self, world: &mut World);
 
-    fn undo(&mut self, entity: &mut EntityWorldMut);
+    fn remove_dropped(&mut self, _scene_entity: &mut DynamicEntity) {}
 }
 
-struct ComponentMapperImpl<T: Component, M: MapComponent<T>>(M, PhantomData<T>);
+struct ComponentMapperImpl<T: Component, M: MapComponentDyn<T>> {
+    mapper: M,
+    applied: EntityHashSet,
+    dropped: EntityHashSet,
+    snapshots: EntityHashMap<M::Output>,
+    marker: PhantomData<T>,
+}
 
-impl<T: Component, M: MapComponent<T>> ComponentMapperImpl<T, M> {
-    fn new(m: M) -> Self {
-        Self(m, PhantomData)
+impl<T: Component, M: MapComponentDyn<T>> ComponentMapperImpl<T, M> {
+    fn new(mapper: M) -> Self {
+        Self {
+            mapper,
+            applied: EntityHashSet::default(),
+            dropped: EntityHashSet::default(),
+            snapshots: EntityHashMap::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns true if the mapped output is a different component than `T`.
+    fn is_distinct() -> bool {
+        TypeId::of::<T>() != TypeId::of::<M::Output>()
     }
 }
 
-impl<T: Component, M: MapComponent<T>> ComponentMapper for ComponentMapperImpl<T, M> {
-    fn apply(&mut self, entity: &mut EntityWorldMut) {
-        if let Some(component) = entity.get::<T>() {
-            entity.insert(self.0.map_component(component));
+impl<T: Component, M: MapComponentDyn<T>> ComponentMapper for ComponentMapperImpl<T, M> {
+    fn apply(&mut self, entity: &mut EntityWorldMut) -> Result<(), MapError> {
+        let Some(component) = entity.get::<T>() else {
+            return Ok(());
+        };
+
+        let context = MapContext {
+            entity: entity.id(),
+            world: entity.world(),
+        };
+        let Some(output) = self.mapper.try_map(component, context)? else {
+            self.dropped.insert(entity.id());
+            return Ok(());
+        };
+
+        if let Some(existing) = entity.take::<M::Output>() {
+            if Self::is_distinct() {
+                warn!(
+                    "{:?} already has {} before save; it will be restored after save",
+                    entity.id(),
+                    type_name::<M::Output>()
+                );
+            }
+            self.snapshots.insert(entity.id(), existing);
+        }
+        entity.insert(output);
+        self.applied.insert(entity.id());
+        Ok(())
+    }
+
+    fn replace(&mut self, entity: &mut EntityWorldMut) -> Result<(), MapError> {
+        let Some(component) = entity.get::<T>() else {
+            return Ok(());
+        };
+
+        let context = MapContext {
+            entity: entity.id(),
+            world: entity.world(),
+        };
+        let output = self.mapper.try_map(component, context)?;
+        entity.remove::<T>();
+
+        let Some(output) = output else {
+            return Ok(());
+        };
+
+        if Self::is_distinct() && entity.contains::<M::Output>() {
+            warn!(
+                "{:?} already has {} before load; it will be replaced by the loaded {}",
+                entity.id(),
+                type_name::<M::Output>(),
+                type_name::<T>()
+            );
         }
+        entity.insert(output);
+        Ok(())
     }
 
-    fn replace(&mut self, entity: &mut EntityWorldMut) {
-        if let Some(component) = entity.take::<T>() {
-            entity.insert(self.0.map_component(&component));
+    fn undo(&mut self, world: &mut World) {
+        for entity in self.applied.drain() {
+            let Ok(mut entity) = world.get_entity_mut(entity) else {
+                continue;
+            };
+
+            entity.remove::<M::Output>();
+            if let Some(existing) = self.snapshots.remove(&entity.id()) {
+                entity.insert(existing);
+            }
         }
+        self.dropped.clear();
     }
 
-    fn undo(&mut self, entity: &mut EntityWorldMut) {
-        entity.remove::<M::Output>();
+    fn remove_dropped(&mut self, scene_entity: &mut DynamicEntity) {
+        if !self.dropped.contains(&scene_entity.entity) {
+            return;
+        }
+
+        scene_entity.components.retain(|component| {
+            component
+                .get_represented_type_info()
+                .is_none_or(|type_info| type_info.type_id() != TypeId::of::<T>())
+        });
     }
 }
 
 type ComponentMapperDyn = Box<dyn ComponentMapper>;
+
+struct BundleMapperImpl<B: BundleInput, M: MapBundle<B>> {
+    mapper: M,
+    applied: EntityHashSet,
+    snapshots: EntityHashMap<M::Output>,
+    marker: PhantomData<B>,
+}
+
+impl<B: BundleInput, M: MapBundle<B>> BundleMapperImpl<B, M> {
+    fn new(mapper: M) -> Self {
+        Self {
+            mapper,
+            applied: EntityHashSet::default(),
+            snapshots: EntityHashMap::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns true if the mapped output is a different bundle than `B`.
+    fn is_distinct() -> bool {
+        TypeId::of::<B>() != TypeId::of::<M::Output>()
+    }
+
+    /// Returns true if the given entity has any component of the mapped output.
+    fn has_any_output(entity: &EntityWorldMut) -> bool {
+        M::Output::get_component_ids(entity.world().components())
+            .flatten()
+            .any(|id| entity.contains_id(id))
+    }
+}
+
+impl<B: BundleInput, M: MapBundle<B>> ComponentMapper for BundleMapperImpl<B, M> {
+    fn apply(&mut self, entity: &mut EntityWorldMut) -> Result<(), MapError> {
+        let Some(bundle) = B::from_entity(entity.as_readonly()) else {
+            return Ok(());
+        };
+
+        let output = self.mapper.map_bundle(&bundle);
+        if let Some(existing) = entity.take::<M::Output>() {
+            if Self::is_distinct() {
+                warn!(
+                    "{:?} already has {} before save; it will be restored after save",
+                    entity.id(),
+                    type_name::<M::Output>()
+                );
+            }
+            self.snapshots.insert(entity.id(), existing);
+        } else if Self::has_any_output(entity) {
+            // Partial bundles may not be restored, so the entity is left unmapped
+            warn!(
+                "{:?} already has some components of {} before save; it will not be mapped",
+                entity.id(),
+                type_name::<M::Output>()
+            );
+            return Ok(());
+        }
+        entity.insert(output);
+        self.applied.insert(entity.id());
+        Ok(())
+    }
+
+    fn replace(&mut self, entity: &mut EntityWorldMut) -> Result<(), MapError> {
+        let Some(bundle) = entity.take::<B>() else {
+            return Ok(());
+        };
+
+        if Self::is_distinct() && Self::has_any_output(entity) {
+            warn!(
+                "{:?} already has {} before load; it will be replaced by the loaded {}",
+                entity.id(),
+                type_name::<M::Output>(),
+                type_name::<B>()
+            );
+        }
+        entity.insert(self.mapper.map_bundle(&bundle));
+        Ok(())
+    }
+
+    fn undo(&mut self, world: &mut World) {
+        for entity in self.applied.drain() {
+            let Ok(mut entity) = world.get_entity_mut(entity) else {
+                continue;
+            };
+
+            entity.remove::<M::Output>();
+            if let Some(existing) = self.snapshots.remove(&entity.id()) {
+                entity.insert(existing);
+            }
+        }
+    }
+}
+
+trait SceneComponentMapper: Static {
+    fn apply_scene(&self, world: &World, scene_entity: &mut DynamicEntity);
+}
+
+impl<T: Component, M: MapComponentDyn<T>> SceneComponentMapper for ComponentMapperImpl<T, M>
+where
+    M::Output: Reflect,
+{
+    fn apply_scene(&self, world: &World, scene_entity: &mut DynamicEntity) {
+        let Some(component) = world.get::<T>(scene_entity.entity) else {
+            return;
+        };
+
+        let context = MapContext {
+            entity: scene_entity.entity,
+            world,
+        };
+        let output = match self.mapper.try_map(component, context) {
+            Ok(output) => output,
+            Err(error) => {
+                error!("failed to map {}: {error}", type_name::<T>());
+                return;
+            }
+        };
+
+        scene_entity.components.retain(|component| {
+            component
+                .get_represented_type_info()
+                .is_none_or(|type_info| type_info.type_id() != TypeId::of::<T>())
+        });
+
+        let Some(output) = output else {
+            return;
+        };
+
+        let len = scene_entity.components.len();
+        scene_entity.components.retain(|component| {
+            component
+                .get_represented_type_info()
+                .is_none_or(|type_info| type_info.type_id() != TypeId::of::<M::Output>())
+        });
+        if scene_entity.components.len() != len {
+            warn!(
+                "{:?} already has {} in saved data; it will be replaced by the mapped {}",
+                scene_entity.entity,
+                type_name::<M::Output>(),
+                type_name::<T>()
+            );
+        }
+        scene_entity.components.push(Box::new(output));
+    }
+}
+
+type SceneComponentMapperDyn = Box<dyn SceneComponentMapper>;
+
+trait ResourceMapper: Static {
+    fn apply(&mut self, world: &mut World);
+
+    fn replace(&mut self, world: &mut World);
+
+    fn undo(&mut self, world: &mut World);
+}
+
+struct ResourceMapperImpl<R: Resource, M: MapResource<R>> {
+    mapper: M,
+    applied: bool,
+    snapshot: Option<M::Output>,
+    marker: PhantomData<R>,
+}
+
+impl<R: Resource, M: MapResource<R>> ResourceMapperImpl<R, M> {
+    fn new(mapper: M) -> Self {
+        Self {
+            mapper,
+            applied: false,
+            snapshot: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns true if the mapped output is a different resource than `R`.
+    fn is_distinct() -> bool {
+        TypeId::of::<R>() != TypeId::of::<M::Output>()
+    }
+}
+
+impl<R: Resource, M: MapResource<R>> ResourceMapper for ResourceMapperImpl<R, M> {
+    fn apply(&mut self, world: &mut World) {
+        let Some(resource) = world.get_resource::<R>() else {
+            return;
+        };
+
+        let output = self.mapper.map_resource(resource);
+        if let Some(existing) = world.remove_resource::<M::Output>() {
+            if Self::is_distinct() {
+                warn!(
+                    "{} already exists before save; it will be restored after save",
+                    type_name::<M::Output>()
+                );
+            }
+            self.snapshot = Some(existing);
+        }
+        world.insert_resource(output);
+        self.applied = true;
+    }
+
+    fn replace(&mut self, world: &mut World) {
+        let Some(resource) = world.remove_resource::<R>() else {
+            return;
+        };
+
+        if Self::is_distinct() && world.contains_resource::<M::Output>() {
+            warn!(
+                "{} already exists before load; it will be replaced by the loaded {}",
+                type_name::<M::Output>(),
+                type_name::<R>()
+            );
+        }
+        let output = self.mapper.map_resource(&resource);
+        world.insert_resource(output);
+    }
+
+    fn undo(&mut self, world: &mut World) {
+        if !std::mem::take(&mut self.applied) {
+            return;
+        }
+
+        world.remove_resource::<M::Output>();
+        if let Some(existing) = self.snapshot.take() {
+            world.insert_resource(existing);
+        }
+    }
+}
+
+type ResourceMapperDyn = Box<dyn ResourceMapper>;
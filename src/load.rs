@@ -1,13 +1,19 @@
+use std::any::TypeId;
+use std::hash::Hash;
 use std::io::{self, Read};
 use std::marker::PhantomData;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use bevy_scene::DynamicScene;
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::{FromReflect, PartialReflect, ReflectRef, TypeInfo, TypePath};
+use bevy_scene::{DynamicEntity, DynamicScene, SceneFilter};
 use moonshine_util::expect::{expect_deferred, ExpectDeferred};
 use moonshine_util::Static;
 use serde::de::DeserializeSeed;
 
-use bevy_ecs::entity::EntityHashMap;
+use bevy_ecs::entity::{EntityHashMap, EntityHashSet};
 use bevy_ecs::prelude::*;
 use bevy_ecs::query::QueryFilter;
 use bevy_log::prelude::*;
@@ -16,8 +22,11 @@ use bevy_scene::{serde::SceneDeserializer, SceneSpawnError};
 use moonshine_util::event::{OnSingle, SingleEvent, TriggerSingle};
 use thiserror::Error;
 
-use crate::save::Save;
-use crate::{MapComponent, SceneMapper};
+use crate::save::{remove_references, Save};
+use crate::{
+    BundleInput, MapBundle, MapComponent, MapComponentWithContext, MapError, MapResource,
+    SceneMapper, TryMapComponent,
+};
 
 /// A [`Component`] which marks its [`Entity`] to be despawned prior to load.
 ///
@@ -60,8 +69,103 @@ use crate::{MapComponent, SceneMapper};
 ///     }
 /// }
 /// ```
+///
+/// # Domains
+///
+/// Similar to [`Save`], each `Unload` marker belongs to a save domain `D`.
+/// Entities are only unloaded when loading data of their own domain.
+#[derive(Component)]
+pub struct Unload<D: Static = ()> {
+    domain: PhantomData<D>,
+}
+
+impl<D: Static> Unload<D> {
+    /// Creates a new [`Unload`] marker for the domain `D`.
+    pub const fn new() -> Self {
+        Self {
+            domain: PhantomData,
+        }
+    }
+}
+
+impl<D: Static> Default for Unload<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Static> Clone for Unload<D> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+/// The [`Unload`] marker of the default domain.
+///
+/// This allows the marker to be used as a value, such as `commands.spawn(Unload)`.
+#[allow(non_upper_case_globals)]
+pub const Unload: Unload = Unload::new();
+
+/// A [`Component`] which exempts its [`Entity`] and all of its descendants from being unloaded.
+///
+/// # Usage
+/// Some entities, such as a camera rig or a network connection, must persist through the load process,
+/// even if they are marked with [`Save`] or [`Unload`].
+///
+/// By default, the saved data of kept entities is spawned as new entities, next to the kept entities.
+/// To apply the saved data to the kept entities in place instead, match them by a persistent ID component
+/// using [`LoadWorld::match_kept_by`]. This does not apply to [additive](LoadMode::Additive) loads.
+///
+/// If the parent of a kept entity is unloaded, the kept entity is detached from its parent before unloading.
+#[derive(Component, Default, Clone)]
+pub struct KeepOnLoad;
+
+/// A [`Component`] which determines how its [`Entity`] is unloaded prior to load.
+///
+/// By default, all unloaded entities are despawned.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use bevy::scene::SceneFilter;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component, Default)]
+/// struct Selection(Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[require(
+///     Unload,
+///     Selection,
+///     UnloadPolicy = UnloadPolicy::Reset(SceneFilter::deny_all().allow::<Selection>())
+/// )]
+/// struct SelectionView; // <-- Expensive to rebuild, so keep it and only clear its state
+/// ```
 #[derive(Component, Default, Clone)]
-pub struct Unload;
+pub enum UnloadPolicy {
+    /// Despawn the entity.
+    ///
+    /// This is the default policy.
+    #[default]
+    Despawn,
+    /// Keep the entity as is.
+    Keep,
+    /// Keep the entity, but reset all of its components selected by the given [`SceneFilter`] to their default value.
+    ///
+    /// Components must reflect [`Component`] and [`Default`](bevy_reflect::std_traits::ReflectDefault) to be reset.
+    /// Otherwise, they are kept and a warning is reported.
+    Reset(SceneFilter),
+    /// Invoke the given function on the entity.
+    Custom(Arc<dyn Fn(EntityWorldMut) + Send + Sync>),
+}
+
+impl UnloadPolicy {
+    /// Creates a new [`UnloadPolicy::Custom`] with the given function.
+    pub fn custom(f: impl Fn(EntityWorldMut) + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(f))
+    }
+}
 
 /// A trait used to trigger a [`LoadEvent`] via [`Commands`] or [`World`].
 pub trait TriggerLoad {
@@ -83,7 +187,8 @@ impl TriggerLoad for &mut World {
 }
 
 /// A [`QueryFilter`] which determines which entities should be unloaded before the load process begins.
-pub type DefaultUnloadFilter = Or<(With<Save>, With<Unload>)>;
+/// This includes all entities with the [`Save`] or [`Unload`] component of the given domain.
+pub type DefaultUnloadFilter<D = ()> = Or<(With<Save<D>>, With<Unload<D>>)>;
 
 /// A [`SingleEvent`] which starts the load process with the given parameters.
 ///
@@ -105,15 +210,61 @@ pub trait LoadEvent: SingleEvent {
 
     /// Called once before unloading entities.
     ///
-    /// All given entities will be despawned after this call.
+    /// All given entities will be unloaded according to their [`UnloadPolicy`] after this call.
     /// This is useful if you want to update the world state as a result of unloading these entities.
     fn before_unload(&mut self, _world: &mut World, _entities: &[Entity]) {}
 
+    /// Return `true` if the given saved entity should be loaded.
+    fn filter_entity(&self, _entity: &DynamicEntity) -> bool {
+        true
+    }
+
+    /// Returns a [`SceneFilter`] for selecting which components should be loaded.
+    fn component_filter(&mut self) -> SceneFilter {
+        SceneFilter::allow_all()
+    }
+
+    /// Returns a [`SceneFilter`] for selecting which resources should be loaded.
+    fn resource_filter(&mut self) -> SceneFilter {
+        SceneFilter::allow_all()
+    }
+
+    /// Returns the [`MissingResourcePolicy`] for resources which are explicitly allowed by the
+    /// [resource filter](LoadEvent::resource_filter) but missing from the saved data.
+    ///
+    /// The policy is only applied if the resource filter is an allowlist.
+    fn missing_resource_policy(&self) -> MissingResourcePolicy {
+        MissingResourcePolicy::Keep
+    }
+
+    /// Returns a map of saved entities to existing entities which should be updated in place.
+    ///
+    /// Any saved entity which is not in this map is spawned as a new entity.
+    /// Existing entities in this map are never unloaded.
+    fn entity_map(&mut self, _world: &mut World, _scene: &DynamicScene) -> EntityHashMap<Entity> {
+        EntityHashMap::default()
+    }
+
+    /// Return `true` if the given [`Entity`] should be unloaded.
+    ///
+    /// This is only called for entities which match the [`UnloadFilter`](LoadEvent::UnloadFilter).
+    fn filter_unload(&self, _entity: EntityRef) -> bool {
+        true
+    }
+
     /// Called for all entities after they have been loaded.
     ///
     /// This is useful to undo any modifications done before loading.
     /// You also have access to [`Loaded`] here for any additional post-processing before [`OnLoad`] is triggered.
     fn after_load(&mut self, _world: &mut World, _result: &LoadResult) {}
+
+    /// Returns an error which occurred during [`LoadEvent::after_load`], if any.
+    ///
+    /// If an error is returned, it is returned as the result of the load process.
+    /// Note that the loaded world is not reverted.
+    fn take_error(&mut self) -> Option<LoadError> {
+        None
+    }
 }
 
 /// A generic [`LoadEvent`] which loads the world from a file or stream.
@@ -122,6 +273,39 @@ pub struct LoadWorld<U: QueryFilter = DefaultUnloadFilter> {
     pub input: LoadInput,
     /// A [`SceneMapper`] used to map components after the load process.
     pub mapper: SceneMapper,
+    /// A predicate for selecting which saved entities should be loaded.
+    ///
+    /// By default, all saved entities are selected.
+    pub entities: Box<dyn Fn(&DynamicEntity) -> bool + Send + Sync>,
+    /// A filter for selecting which resources should be loaded.
+    ///
+    /// By default, all saved resources are selected.
+    pub resources: SceneFilter,
+    /// Determines what happens to selected resources which are missing from the saved data.
+    ///
+    /// See [`MissingResourcePolicy`] for more information.
+    pub missing_resources: MissingResourcePolicy,
+    /// A filter for selecting which components should be loaded.
+    ///
+    /// By default, all saved components are selected.
+    pub components: SceneFilter,
+    /// Determines how the loaded entities are applied to the world.
+    ///
+    /// See [`LoadMode`] for more information.
+    pub mode: LoadMode,
+    /// An optional marker inserted into every loaded entity.
+    ///
+    /// This is useful for marking loaded entities with [`Save`] again, since it is never serialized.
+    /// By default, this is set to [`Save`] for [`LoadWorld::default_from_file`] and [`LoadWorld::default_from_stream`].
+    pub marker: Option<LoadMarkerFn>,
+    /// An optional anchor [`Entity`] for the loaded entities.
+    ///
+    /// If set, all spawned root entities of the loaded scene become children of this entity.
+    pub anchor: Option<Entity>,
+    /// An optional strategy for matching saved entities with existing [`KeepOnLoad`] entities.
+    ///
+    /// See [`LoadWorld::match_kept_by`] for more information.
+    pub kept: Option<MergeBy>,
     #[doc(hidden)]
     pub unload: PhantomData<U>,
 }
@@ -132,6 +316,14 @@ impl<U: QueryFilter> LoadWorld<U> {
         LoadWorld {
             input,
             mapper,
+            entities: Box::new(|_| true),
+            resources: SceneFilter::allow_all(),
+            missing_resources: MissingResourcePolicy::Keep,
+            components: SceneFilter::allow_all(),
+            mode: LoadMode::Replace,
+            marker: None,
+            anchor: None,
+            kept: None,
             unload: PhantomData,
         }
     }
@@ -142,6 +334,14 @@ impl<U: QueryFilter> LoadWorld<U> {
         LoadWorld {
             input: LoadInput::File(path.into()),
             mapper: SceneMapper::default(),
+            entities: Box::new(|_| true),
+            resources: SceneFilter::allow_all(),
+            missing_resources: MissingResourcePolicy::Keep,
+            components: SceneFilter::allow_all(),
+            mode: LoadMode::Replace,
+            marker: None,
+            anchor: None,
+            kept: None,
             unload: PhantomData,
         }
     }
@@ -152,6 +352,14 @@ impl<U: QueryFilter> LoadWorld<U> {
         LoadWorld {
             input: LoadInput::Stream(Box::new(stream)),
             mapper: SceneMapper::default(),
+            entities: Box::new(|_| true),
+            resources: SceneFilter::allow_all(),
+            missing_resources: MissingResourcePolicy::Keep,
+            components: SceneFilter::allow_all(),
+            mode: LoadMode::Replace,
+            marker: None,
+            anchor: None,
+            kept: None,
             unload: PhantomData,
         }
     }
@@ -163,19 +371,281 @@ impl<U: QueryFilter> LoadWorld<U> {
             ..self
         }
     }
+
+    /// Maps the given components into another [`Bundle`] using a [bundle mapper](MapBundle) after loading.
+    pub fn map_bundle<B: BundleInput>(self, m: impl MapBundle<B>) -> Self {
+        LoadWorld {
+            mapper: self.mapper.map_bundle(m),
+            ..self
+        }
+    }
+
+    /// Maps the given [`Resource`] into another using a [resource mapper](MapResource) after loading.
+    pub fn map_resource<R: Resource>(self, m: impl MapResource<R>) -> Self {
+        let resources = match self.resources {
+            SceneFilter::Allowlist(_) => self.resources.allow::<R>(),
+            resources => resources,
+        };
+        LoadWorld {
+            resources,
+            mapper: self.mapper.map_resource(m),
+            ..self
+        }
+    }
+
+    /// Maps the given [`Component`] into another using a [fallible component mapper](TryMapComponent) after loading.
+    pub fn try_map_component<T: Component>(self, m: impl TryMapComponent<T>) -> Self {
+        LoadWorld {
+            mapper: self.mapper.try_map(m),
+            ..self
+        }
+    }
+
+    /// Maps the given [`Component`] into another using a [component mapper with context](MapComponentWithContext)
+    /// after loading.
+    pub fn map_component_with_context<T: Component>(
+        self,
+        m: impl MapComponentWithContext<T>,
+    ) -> Self {
+        LoadWorld {
+            mapper: self.mapper.map_with_context(m),
+            ..self
+        }
+    }
+
+    /// Loads only the saved entities which match the given predicate.
+    pub fn filter_entities(
+        self,
+        predicate: impl Fn(&DynamicEntity) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        LoadWorld {
+            entities: Box::new(predicate),
+            ..self
+        }
+    }
+
+    /// Loads only the saved entities which contain the given [`Component`].
+    pub fn include_entities_with<T: Component>(self) -> Self {
+        self.filter_entities(|entity| {
+            entity.components.iter().any(|component| {
+                component
+                    .get_represented_type_info()
+                    .is_some_and(|type_info| type_info.type_id() == TypeId::of::<T>())
+            })
+        })
+    }
+
+    /// Includes the given [`Resource`] in the loaded data.
+    ///
+    /// By default, all saved resources are loaded.
+    /// Once any resource is included, only the included resources are loaded.
+    pub fn include_resource<R: Resource>(self) -> Self {
+        self.include_resource_by_id(TypeId::of::<R>())
+    }
+
+    /// Includes the given [`Resource`] by its [`TypeId`] in the loaded data.
+    ///
+    /// See [`LoadWorld::include_resource`] for more information.
+    pub fn include_resource_by_id(self, type_id: TypeId) -> Self {
+        let resources = match self.resources {
+            SceneFilter::Allowlist(_) => self.resources,
+            SceneFilter::Unset | SceneFilter::Denylist(_) => SceneFilter::deny_all(),
+        };
+        LoadWorld {
+            resources: resources.allow_by_id(type_id),
+            ..self
+        }
+    }
+
+    /// Sets the [`MissingResourcePolicy`] for included resources which are missing from the saved data.
+    ///
+    /// Only resources which are explicitly included with [`LoadWorld::include_resource`] are affected.
+    pub fn on_missing_resources(self, policy: MissingResourcePolicy) -> Self {
+        LoadWorld {
+            missing_resources: policy,
+            ..self
+        }
+    }
+
+    /// Excludes the given [`Resource`] from the loaded data.
+    pub fn exclude_resource<R: Resource>(self) -> Self {
+        self.exclude_resource_by_id(TypeId::of::<R>())
+    }
+
+    /// Excludes the given [`Resource`] by its [`TypeId`] from the loaded data.
+    pub fn exclude_resource_by_id(self, type_id: TypeId) -> Self {
+        LoadWorld {
+            resources: self.resources.deny_by_id(type_id),
+            ..self
+        }
+    }
+
+    /// Excludes the given [`Component`] from the loaded data.
+    pub fn exclude_component<T: Component>(self) -> Self {
+        self.exclude_component_by_id(TypeId::of::<T>())
+    }
+
+    /// Excludes the given [`Component`] by its [`TypeId`] from the loaded data.
+    pub fn exclude_component_by_id(self, type_id: TypeId) -> Self {
+        LoadWorld {
+            components: self.components.deny_by_id(type_id),
+            ..self
+        }
+    }
+
+    /// Spawns the loaded entities alongside existing entities without unloading anything.
+    ///
+    /// See [`LoadMode::Additive`] for more information.
+    pub fn additive(self) -> Self {
+        LoadWorld {
+            mode: LoadMode::Additive,
+            ..self
+        }
+    }
+
+    /// Inserts the given [`Bundle`] into every loaded entity.
+    pub fn with_marker(self, marker: impl Bundle + Clone) -> Self {
+        LoadWorld {
+            marker: Some(Box::new(move |entity| {
+                entity.insert(marker.clone());
+            })),
+            ..self
+        }
+    }
+
+    /// Disables the insertion of any marker into loaded entities.
+    pub fn without_marker(self) -> Self {
+        LoadWorld {
+            marker: None,
+            ..self
+        }
+    }
+
+    /// Inserts all root entities of the loaded scene as children of the given anchor [`Entity`].
+    ///
+    /// Root entities are spawned entities without a [`ChildOf`] component.
+    /// Existing entities which are [merged](LoadMode::Merge) with the loaded data are never reparented.
+    pub fn with_anchor(self, anchor: Entity) -> Self {
+        LoadWorld {
+            anchor: Some(anchor),
+            ..self
+        }
+    }
+
+    /// Merges the loaded entities into existing entities using the given [`MergeBy`] strategy.
+    ///
+    /// See [`LoadMode::Merge`] for more information.
+    pub fn merge(self, merge: MergeBy) -> Self {
+        LoadWorld {
+            mode: LoadMode::Merge(merge),
+            ..self
+        }
+    }
+
+    /// Merges the loaded entities into existing entities with a matching persistent ID component `T`.
+    ///
+    /// See [`MergeBy`] for more information.
+    pub fn merge_by<T: MergeId>(self) -> Self {
+        self.merge(MergeBy::new::<T>())
+    }
+
+    /// Matches saved entities with existing [`KeepOnLoad`] entities by a persistent ID component `T`.
+    ///
+    /// Matched kept entities are updated in place with their saved data, instead of spawning a copy.
+    /// Unlike [`LoadWorld::merge_by`], this only matches kept entities, and does not change the [`LoadMode`].
+    pub fn match_kept_by<T: MergeId>(self) -> Self {
+        LoadWorld {
+            kept: Some(MergeBy::new::<T>()),
+            ..self
+        }
+    }
 }
 
 impl LoadWorld {
     /// Creates a new [`LoadWorld`] event which unloads default entities (with [`Unload`] or [`Save`])
     /// before loading the file at the given path.
+    ///
+    /// All loaded entities are marked with [`Save`].
     pub fn default_from_file(path: impl Into<PathBuf>) -> Self {
-        Self::from_file(path)
+        Self::from_file(path).with_marker(Save)
     }
 
     /// Creates a new [`LoadWorld`] event which unloads default entities (with [`Unload`] or [`Save`])
     /// before loading from the given [`Read`] stream.
+    ///
+    /// All loaded entities are marked with [`Save`].
     pub fn default_from_stream(stream: impl LoadStream) -> Self {
-        Self::from_stream(stream)
+        Self::from_stream(stream).with_marker(Save)
+    }
+
+    /// Creates a new [`LoadWorld`] event which unloads entities of the given domain (with [`Unload<D>`] or [`Save<D>`])
+    /// before loading the file at the given path.
+    ///
+    /// All loaded entities are marked with [`Save<D>`].
+    /// See [`Save`] for more information about domains.
+    pub fn domain_from_file<D: Static>(
+        path: impl Into<PathBuf>,
+    ) -> LoadWorld<DefaultUnloadFilter<D>> {
+        LoadWorld::from_file(path).with_marker(Save::<D>::new())
+    }
+
+    /// Creates a new [`LoadWorld`] event which unloads entities of the given domain (with [`Unload<D>`] or [`Save<D>`])
+    /// before loading from the given [`Read`] stream.
+    ///
+    /// All loaded entities are marked with [`Save<D>`].
+    /// See [`Save`] for more information about domains.
+    pub fn domain_from_stream<D: Static>(
+        stream: impl LoadStream,
+    ) -> LoadWorld<DefaultUnloadFilter<D>> {
+        LoadWorld::from_stream(stream).with_marker(Save::<D>::new())
+    }
+
+    /// Creates a new [`LoadWorld`] event which loads the file at the given path
+    /// without unloading any entities.
+    ///
+    /// All loaded entities are marked with [`Save`].
+    pub fn additive_from_file(path: impl Into<PathBuf>) -> Self {
+        Self::from_file(path).additive().with_marker(Save)
+    }
+
+    /// Creates a new [`LoadWorld`] event which loads from the given [`Read`] stream
+    /// without unloading any entities.
+    ///
+    /// All loaded entities are marked with [`Save`].
+    pub fn additive_from_stream(stream: impl LoadStream) -> Self {
+        Self::from_stream(stream).additive().with_marker(Save)
+    }
+
+    /// Creates a new [`LoadWorld`] event which loads the given input additively
+    /// and inserts all of its root entities as children of the given anchor [`Entity`].
+    ///
+    /// All loaded entities are marked with [`Save`].
+    ///
+    /// This is useful for using saved data as a reusable blueprint.
+    /// Each load spawns a new set of entities. Use [`LoadInput::shared`] to load the same
+    /// [`DynamicScene`] multiple times.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use bevy::prelude::*;
+    /// use moonshine_save::prelude::*;
+    ///
+    /// #[derive(Resource)]
+    /// struct RoomBlueprint(Arc<DynamicScene>);
+    ///
+    /// fn spawn_room(blueprint: Res<RoomBlueprint>, mut commands: Commands) {
+    ///     let anchor = commands.spawn(Transform::default()).id();
+    ///     let input = LoadInput::shared(blueprint.0.clone());
+    ///     commands.trigger_load(LoadWorld::instantiate(input, anchor));
+    /// }
+    /// ```
+    pub fn instantiate(input: LoadInput, anchor: Entity) -> Self {
+        Self::new(input, SceneMapper::default())
+            .additive()
+            .with_marker(Save)
+            .with_anchor(anchor)
     }
 }
 
@@ -195,8 +665,56 @@ where
         world.insert_resource(ExpectDeferred);
     }
 
+    fn filter_entity(&self, entity: &DynamicEntity) -> bool {
+        (self.entities)(entity)
+    }
+
+    fn component_filter(&mut self) -> SceneFilter {
+        std::mem::replace(&mut self.components, SceneFilter::Unset)
+    }
+
+    fn resource_filter(&mut self) -> SceneFilter {
+        std::mem::replace(&mut self.resources, SceneFilter::Unset)
+    }
+
+    fn take_error(&mut self) -> Option<LoadError> {
+        self.mapper.take_error().map(LoadError::Map)
+    }
+
+    fn missing_resource_policy(&self) -> MissingResourcePolicy {
+        self.missing_resources
+    }
+
+    fn entity_map(&mut self, world: &mut World, scene: &DynamicScene) -> EntityHashMap<Entity> {
+        let mut entity_map = match &mut self.mode {
+            LoadMode::Replace => EntityHashMap::default(),
+            LoadMode::Additive => return EntityHashMap::default(),
+            LoadMode::Merge(merge) => merge.matcher.match_entities(world, scene, |_, _| true),
+        };
+
+        if let Some(kept) = &mut self.kept {
+            let matched: EntityHashSet = entity_map.values().copied().collect();
+            for (saved, entity) in kept.matcher.match_entities(world, scene, is_kept) {
+                if !matched.contains(&entity) {
+                    entity_map.entry(saved).or_insert(entity);
+                }
+            }
+        }
+
+        entity_map
+    }
+
+    fn filter_unload(&self, entity: EntityRef) -> bool {
+        match &self.mode {
+            LoadMode::Replace => true,
+            LoadMode::Additive => false,
+            LoadMode::Merge(merge) => merge.despawn_missing && merge.matcher.has_id(entity),
+        }
+    }
+
     fn after_load(&mut self, world: &mut World, result: &LoadResult) {
         if let Ok(loaded) = result {
+            self.mapper.add_load_mappings(world);
             for entity in loaded.entities() {
                 let Ok(entity) = world.get_entity_mut(entity) else {
                     // Some entities may be invalid during load. See `unsaved.rs` test.
@@ -204,12 +722,201 @@ where
                 };
                 self.mapper.replace(entity);
             }
+            self.mapper.replace_resources(world);
+
+            if let Some(marker) = &self.marker {
+                for entity in loaded.entities() {
+                    if let Ok(mut entity) = world.get_entity_mut(entity) {
+                        marker(&mut entity);
+                    }
+                }
+            }
+
+            if let Some(anchor) = self.anchor {
+                for entity in loaded.spawned_entities() {
+                    let Ok(mut entity) = world.get_entity_mut(entity) else {
+                        continue;
+                    };
+                    if !entity.contains::<ChildOf>() {
+                        entity.insert(ChildOf(anchor));
+                    }
+                }
+            }
         }
 
         expect_deferred(world);
     }
 }
 
+type LoadMarkerFn = Box<dyn Fn(&mut EntityWorldMut) + Send + Sync>;
+
+/// Determines how a [`LoadWorld`] event applies the loaded entities to the [`World`].
+#[derive(Default)]
+pub enum LoadMode {
+    /// Unloads all entities which match the [`UnloadFilter`](LoadEvent::UnloadFilter),
+    /// and then spawns all loaded entities as new entities.
+    ///
+    /// This is the default mode.
+    #[default]
+    Replace,
+    /// Spawns all loaded entities as new entities without unloading anything.
+    ///
+    /// This is useful for loading saved data (such as prefabs) into an existing world.
+    /// All spawned entities are reported in [`Loaded`].
+    Additive,
+    /// Matches loaded entities with existing entities using a persistent ID component.
+    ///
+    /// Matched entities are updated in place and keep their [`Entity`] ID, which means any
+    /// external references to them remain valid. Unmatched entities are spawned as new entities.
+    ///
+    /// Entities which match the [`UnloadFilter`](LoadEvent::UnloadFilter) are **NOT** unloaded
+    /// in this mode, unless they have a persistent ID which is missing from the saved data
+    /// and [`MergeBy::despawn_missing`] is set.
+    Merge(MergeBy),
+}
+
+/// Determines what happens to resources which are selected for loading, but missing from the saved data.
+///
+/// This is useful to ensure loading data which was saved before a resource existed behaves deterministically.
+///
+/// This policy only applies to resources which are explicitly included with [`LoadWorld::include_resource`].
+/// If no resources are included, it has no effect.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissingResourcePolicy {
+    /// Keep the current value of the resource.
+    ///
+    /// This is the default policy.
+    #[default]
+    Keep,
+    /// Remove the resource from the world.
+    Remove,
+    /// Reset the resource to its default value.
+    ///
+    /// The resource must reflect [`Default`](bevy_reflect::std_traits::ReflectDefault).
+    /// Otherwise, it is kept and a warning is reported.
+    Reset,
+}
+
+/// A strategy for merging loaded entities into existing entities by a persistent ID component.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component, Reflect, Clone, PartialEq, Eq, Hash)]
+/// #[reflect(Component)]
+/// struct PersistentId(u64);
+///
+/// fn load(mut commands: Commands) {
+///     commands.trigger_load(
+///         LoadWorld::default_from_file("saved.ron")
+///             .merge(MergeBy::new::<PersistentId>().despawn_missing()),
+///     );
+/// }
+/// ```
+pub struct MergeBy {
+    matcher: Box<dyn EntityMatcher>,
+    despawn_missing: bool,
+}
+
+impl MergeBy {
+    /// Creates a new [`MergeBy`] strategy which matches entities by the given ID component `T`.
+    ///
+    /// The ID component must be saved for this to work.
+    /// If multiple existing entities share the same ID, none of them are matched and a warning is reported.
+    pub fn new<T: MergeId>() -> Self {
+        Self {
+            matcher: Box::new(EntityMatcherImpl::<T>(PhantomData)),
+            despawn_missing: false,
+        }
+    }
+
+    /// Despawns existing entities with an ID which is missing from the saved data.
+    ///
+    /// By default, these entities are kept.
+    pub fn despawn_missing(mut self) -> Self {
+        self.despawn_missing = true;
+        self
+    }
+}
+
+/// A [`Component`] which may be used as a persistent ID to [merge](MergeBy) loaded entities with existing ones.
+pub trait MergeId: Component + FromReflect + TypePath + Clone + Eq + Hash {}
+
+impl<T: Component + FromReflect + TypePath + Clone + Eq + Hash> MergeId for T {}
+
+trait EntityMatcher: Static {
+    fn match_entities(
+        &mut self,
+        world: &mut World,
+        scene: &DynamicScene,
+        filter: fn(&World, Entity) -> bool,
+    ) -> EntityHashMap<Entity>;
+
+    fn has_id(&self, entity: EntityRef) -> bool;
+}
+
+struct EntityMatcherImpl<T: MergeId>(PhantomData<T>);
+
+impl<T: MergeId> EntityMatcher for EntityMatcherImpl<T> {
+    fn match_entities(
+        &mut self,
+        world: &mut World,
+        scene: &DynamicScene,
+        filter: fn(&World, Entity) -> bool,
+    ) -> EntityHashMap<Entity> {
+        let mut existing: HashMap<T, Entity> = HashMap::default();
+        let mut duplicates: HashSet<T> = HashSet::default();
+        let mut query = world.query::<(Entity, &T)>();
+        for (entity, id) in query.iter(world) {
+            if !filter(world, entity) {
+                continue;
+            }
+
+            if existing.insert(id.clone(), entity).is_some() {
+                duplicates.insert(id.clone());
+            }
+        }
+
+        if !duplicates.is_empty() {
+            warn!(
+                "{} {} IDs are shared by multiple existing entities and will not be merged",
+                duplicates.len(),
+                T::type_path()
+            );
+            for id in &duplicates {
+                existing.remove(id);
+            }
+        }
+
+        let mut entity_map = EntityHashMap::default();
+        for scene_entity in &scene.entities {
+            let id = scene_entity.components.iter().find_map(|component| {
+                let type_info = component.get_represented_type_info()?;
+                if type_info.type_id() != TypeId::of::<T>() {
+                    return None;
+                }
+                T::from_reflect(component.as_partial_reflect())
+            });
+
+            let Some(id) = id else {
+                continue;
+            };
+
+            if let Some(entity) = existing.remove(&id) {
+                entity_map.insert(scene_entity.entity, entity);
+            }
+        }
+
+        entity_map
+    }
+
+    fn has_id(&self, entity: EntityRef) -> bool {
+        entity.contains::<T>()
+    }
+}
+
 /// Input of the load process.
 pub enum LoadInput {
     /// Load from a file at the given path.
@@ -220,6 +927,10 @@ pub enum LoadInput {
     ///
     /// This is useful if you would like to deserialize the scene manually from any data source.
     Scene(DynamicScene),
+    /// Load from a shared [`DynamicScene`].
+    ///
+    /// The scene is cloned for each load, which allows the same scene to be loaded multiple times.
+    Shared(Arc<DynamicScene>),
     #[doc(hidden)]
     Invalid,
 }
@@ -235,6 +946,11 @@ impl LoadInput {
         Self::Stream(Box::new(stream))
     }
 
+    /// Creates a new [`LoadInput`] which loads from a shared [`DynamicScene`].
+    pub fn shared(scene: impl Into<Arc<DynamicScene>>) -> Self {
+        Self::Shared(scene.into())
+    }
+
     /// Invalidates this [`LoadInput`] and returns it if it was valid.
     pub fn consume(&mut self) -> Option<LoadInput> {
         let input = std::mem::replace(self, LoadInput::Invalid);
@@ -261,6 +977,11 @@ impl<S: Read> LoadStream for S where S: Static {}
 pub struct Loaded {
     /// The map of all loaded entities and their new entity IDs.
     pub entity_map: EntityHashMap<Entity>,
+    /// The set of existing entities which were updated in place, rather than spawned.
+    ///
+    /// This is empty unless entities are [merged](LoadMode::Merge) with the loaded data,
+    /// or [kept entities](KeepOnLoad) are [matched](LoadWorld::match_kept_by).
+    pub merged: EntityHashSet,
 }
 
 impl Loaded {
@@ -271,6 +992,19 @@ impl Loaded {
     pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
         self.entity_map.values().copied()
     }
+
+    /// Iterates over all loaded entities which were spawned by the load process.
+    pub fn spawned_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities()
+            .filter(|entity| !self.merged.contains(entity))
+    }
+
+    /// Iterates over all loaded entities which existed before the load process and were updated in place.
+    ///
+    /// See [`LoadMode::Merge`] for more information.
+    pub fn merged_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.merged.iter().copied()
+    }
 }
 
 #[doc(hidden)]
@@ -289,6 +1023,9 @@ pub enum LoadError {
     /// Indicates a failure to reconstruct the world from the loaded data.
     #[error("Failed to spawn scene: {0}")]
     Scene(SceneSpawnError),
+    /// Indicates an error returned by a [fallible component mapper](TryMapComponent).
+    #[error("Failed to map component: {0}")]
+    Map(MapError),
 }
 
 impl From<io::Error> for LoadError {
@@ -335,7 +1072,7 @@ fn load_world<E: LoadEvent>(mut event: E, world: &mut World) -> LoadResult {
     event.before_load(world);
 
     // Deserialize
-    let scene = match event.input() {
+    let mut scene = match event.input() {
         LoadInput::File(path) => {
             let bytes = std::fs::read(&path)?;
             let mut deserializer = ron::Deserializer::from_bytes(&bytes)?;
@@ -352,33 +1089,286 @@ fn load_world<E: LoadEvent>(mut event: E, world: &mut World) -> LoadResult {
             scene_deserializer.deserialize(&mut deserializer)?
         }
         LoadInput::Scene(scene) => scene,
+        LoadInput::Shared(scene) => clone_scene(&scene),
         LoadInput::Invalid => {
             panic!("LoadInput is invalid");
         }
     };
 
+    // Filter
+    let component_filter = event.component_filter();
+    let resource_filter = event.resource_filter();
+    let mut rejected = EntityHashSet::default();
+    scene.entities.retain(|entity| {
+        let retain = event.filter_entity(entity);
+        if !retain {
+            rejected.insert(entity.entity);
+        }
+        retain
+    });
+    for entity in &mut scene.entities {
+        entity
+            .components
+            .retain(|component| is_allowed(&component_filter, component.as_ref()));
+    }
+    scene
+        .resources
+        .retain(|resource| is_allowed(&resource_filter, resource.as_ref()));
+    restore_defaults(world, &mut scene);
+    if !rejected.is_empty() {
+        remove_references(world, &mut scene, |entity| rejected.contains(&entity));
+    }
+
+    // Merge
+    let mut entity_map = event.entity_map(world, &scene);
+    let merged: EntityHashSet = entity_map.values().copied().collect();
+
     // Unload
     let entities: Vec<_> = world
         .query_filtered::<Entity, E::UnloadFilter>()
         .iter(world)
+        .filter(|entity| !merged.contains(entity))
+        .filter(|entity| !is_kept(world, *entity))
+        .filter(|entity| event.filter_unload(world.entity(*entity)))
         .collect();
+    detach_kept_entities(world, &entities);
     event.before_unload(world, &entities);
     for entity in entities {
-        if let Ok(entity) = world.get_entity_mut(entity) {
-            entity.despawn();
-        }
+        unload_entity(world, entity);
     }
 
     // Load
-    let mut entity_map = EntityHashMap::default();
     scene.write_to_world(world, &mut entity_map)?;
+    apply_missing_resource_policy(
+        world,
+        &scene,
+        &resource_filter,
+        event.missing_resource_policy(),
+    );
     debug!("loaded {} entities", entity_map.len());
 
-    let result = Ok(Loaded { entity_map });
+    let result = Ok(Loaded { entity_map, merged });
     event.after_load(world, &result);
+    if let Ok(loaded) = &result {
+        crate::after_load(world, loaded.entities());
+    }
+    if let Some(error) = event.take_error() {
+        return Err(error);
+    }
     result
 }
 
+/// Returns `true` if the given entity or any of its ancestors has [`KeepOnLoad`].
+fn is_kept(world: &World, entity: Entity) -> bool {
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        if world.entity(entity).contains::<KeepOnLoad>() {
+            return true;
+        }
+        current = world.get::<ChildOf>(entity).map(ChildOf::parent);
+    }
+    false
+}
+
+/// Detaches all entities with [`KeepOnLoad`] which have an unloaded ancestor.
+fn detach_kept_entities(world: &mut World, unloaded: &[Entity]) {
+    let unloaded: EntityHashSet = unloaded.iter().copied().collect();
+    let detached: Vec<_> = world
+        .query_filtered::<Entity, (With<KeepOnLoad>, With<ChildOf>)>()
+        .iter(world)
+        .filter(|&entity| {
+            let mut current = world.get::<ChildOf>(entity).map(ChildOf::parent);
+            while let Some(ancestor) = current {
+                if unloaded.contains(&ancestor) {
+                    return true;
+                }
+                current = world.get::<ChildOf>(ancestor).map(ChildOf::parent);
+            }
+            false
+        })
+        .collect();
+
+    for entity in detached {
+        world.entity_mut(entity).remove::<ChildOf>();
+    }
+}
+
+fn unload_entity(world: &mut World, entity: Entity) {
+    let Ok(entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+
+    let policy = entity.get::<UnloadPolicy>().cloned().unwrap_or_default();
+    match policy {
+        UnloadPolicy::Despawn => entity.despawn(),
+        UnloadPolicy::Keep => {}
+        UnloadPolicy::Reset(filter) => reset_components(entity, &filter),
+        UnloadPolicy::Custom(f) => f(entity),
+    }
+}
+
+fn reset_components(mut entity: EntityWorldMut, filter: &SceneFilter) {
+    let type_ids: Vec<TypeId> = entity
+        .archetype()
+        .components()
+        .iter()
+        .filter_map(|&id| entity.world().components().get_info(id)?.type_id())
+        .filter(|&type_id| filter.is_allowed_by_id(type_id))
+        .collect();
+
+    let type_registry = entity.world().resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    for type_id in type_ids {
+        let Some(registration) = type_registry.get(type_id) else {
+            continue;
+        };
+
+        let (Some(reflect_component), Some(reflect_default)) = (
+            registration.data::<ReflectComponent>(),
+            registration.data::<ReflectDefault>(),
+        ) else {
+            warn!(
+                "component {} does not reflect Component and Default and will not be reset",
+                registration.type_info().type_path()
+            );
+            continue;
+        };
+
+        let value = reflect_default.default();
+        reflect_component.insert(&mut entity, value.as_partial_reflect(), &type_registry);
+    }
+}
+
+fn apply_missing_resource_policy(
+    world: &mut World,
+    scene: &DynamicScene,
+    filter: &SceneFilter,
+    policy: MissingResourcePolicy,
+) {
+    if policy == MissingResourcePolicy::Keep {
+        return;
+    }
+
+    let SceneFilter::Allowlist(included) = filter else {
+        return;
+    };
+
+    let loaded: HashSet<TypeId> = scene
+        .resources
+        .iter()
+        .filter_map(|resource| resource.get_represented_type_info())
+        .map(|type_info| type_info.type_id())
+        .collect();
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    for &type_id in included {
+        if loaded.contains(&type_id) {
+            continue;
+        }
+
+        let Some(registration) = type_registry.get(type_id) else {
+            continue;
+        };
+
+        let Some(reflect_resource) = registration.data::<ReflectResource>() else {
+            continue;
+        };
+
+        if reflect_resource.reflect(&*world).is_err() {
+            continue;
+        }
+
+        match policy {
+            MissingResourcePolicy::Keep => {}
+            MissingResourcePolicy::Remove => {
+                reflect_resource.remove(world);
+            }
+            MissingResourcePolicy::Reset => {
+                let Some(reflect_default) = registration.data::<ReflectDefault>() else {
+                    warn!(
+                        "missing resource {} does not reflect Default and will be kept",
+                        registration.type_info().type_path()
+                    );
+                    continue;
+                };
+                let value = reflect_default.default();
+                reflect_resource.insert(world, value.as_partial_reflect(), &type_registry);
+            }
+        }
+    }
+}
+
+/// Restores all struct fields omitted from the saved data from the [`Default`] value of their type.
+///
+/// This ensures omitted fields are reset even if the loaded value is applied to an existing component or resource.
+fn restore_defaults(world: &World, scene: &mut DynamicScene) {
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    for value in scene
+        .entities
+        .iter_mut()
+        .flat_map(|entity| entity.components.iter_mut())
+        .chain(scene.resources.iter_mut())
+    {
+        let Some(TypeInfo::Struct(info)) = value.get_represented_type_info() else {
+            continue;
+        };
+
+        let ReflectRef::Struct(partial) = value.reflect_ref() else {
+            continue;
+        };
+
+        if partial.field_len() >= info.field_len() {
+            continue;
+        }
+
+        let Some(reflect_default) = type_registry.get_type_data::<ReflectDefault>(info.type_id())
+        else {
+            continue;
+        };
+
+        let mut restored = reflect_default.default();
+        if restored.try_apply(value.as_ref()).is_ok() {
+            *value = restored.into_partial_reflect();
+        }
+    }
+}
+
+fn is_allowed(filter: &SceneFilter, value: &dyn PartialReflect) -> bool {
+    value
+        .get_represented_type_info()
+        .is_none_or(|type_info| filter.is_allowed_by_id(type_info.type_id()))
+}
+
+fn clone_scene(scene: &DynamicScene) -> DynamicScene {
+    fn clone_value(value: &dyn PartialReflect) -> Box<dyn PartialReflect> {
+        value
+            .reflect_clone()
+            .map(PartialReflect::into_partial_reflect)
+            .unwrap_or_else(|_| value.to_dynamic())
+    }
+
+    DynamicScene {
+        resources: scene
+            .resources
+            .iter()
+            .map(|resource| clone_value(resource.as_ref()))
+            .collect(),
+        entities: scene
+            .entities
+            .iter()
+            .map(|entity| DynamicEntity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .iter()
+                    .map(|component| clone_value(component.as_ref()))
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
 // TODO: Documentation
 #[doc(hidden)]
 pub struct LoadCommand<E>(E);
@@ -497,4 +1487,517 @@ mod tests {
 
         remove_file(PATH).unwrap();
     }
+
+    #[test]
+    fn test_load_try_map_component() {
+        pub const PATH: &str = "test_load_try_map_component.ron";
+
+        write(PATH, DATA).unwrap();
+
+        #[derive(Component)]
+        struct Unmapped;
+
+        #[derive(Debug, Error)]
+        #[error("cannot map")]
+        struct CannotMap;
+
+        let mut app = app();
+
+        let loaded = LoadCommand(
+            LoadWorld::default_from_file(PATH).try_map_component(|_: &Foo| None::<Unmapped>),
+        )
+        .apply(app.world_mut());
+        assert!(loaded.is_ok());
+
+        let world = app.world_mut();
+        assert_eq!(world.query::<&Foo>().iter(world).count(), 0);
+        assert_eq!(world.query::<&Unmapped>().iter(world).count(), 0);
+
+        let loaded = LoadCommand(
+            LoadWorld::default_from_file(PATH)
+                .try_map_component(|_: &Foo| Err::<Unmapped, _>(CannotMap)),
+        )
+        .apply(app.world_mut());
+        assert!(matches!(loaded, Err(LoadError::Map(error)) if error.is::<CannotMap>()));
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_load_additive() {
+        pub const PATH: &str = "test_load_additive.ron";
+
+        write(PATH, DATA).unwrap();
+
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let entity = app
+            .world_mut()
+            .run_system_once(|mut commands: Commands| {
+                let entity = commands.spawn((Foo, Save)).id();
+                commands.trigger_load(LoadWorld::additive_from_file(PATH));
+                entity
+            })
+            .unwrap();
+
+        let world = app.world_mut();
+        assert!(world.get_entity(entity).is_ok());
+        assert_eq!(
+            world.query_filtered::<(), With<Foo>>().iter(world).count(),
+            2
+        );
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_load_instantiate() {
+        let scene = Arc::new(DynamicScene {
+            resources: Vec::new(),
+            entities: vec![DynamicEntity {
+                entity: Entity::PLACEHOLDER,
+                components: vec![Box::new(Foo)],
+            }],
+        });
+
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let anchor = app
+            .world_mut()
+            .run_system_once(move |mut commands: Commands| {
+                let anchor = commands.spawn_empty().id();
+                for _ in 0..2 {
+                    let input = LoadInput::shared(scene.clone());
+                    commands.trigger_load(LoadWorld::instantiate(input, anchor));
+                }
+                anchor
+            })
+            .unwrap();
+
+        let world = app.world_mut();
+        let children = world.get::<Children>(anchor).unwrap();
+        assert_eq!(children.len(), 2);
+        assert_ne!(children[0], children[1]);
+        assert!(children
+            .iter()
+            .all(|child| world.entity(child).contains::<Foo>()));
+    }
+
+    #[test]
+    fn test_load_filter() {
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component)]
+        struct Bar;
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component)]
+        struct Baz;
+
+        let scene = DynamicScene {
+            resources: Vec::new(),
+            entities: vec![
+                DynamicEntity {
+                    entity: Entity::from_raw_u32(1).unwrap(),
+                    components: vec![Box::new(Foo), Box::new(Bar), Box::new(Baz)],
+                },
+                DynamicEntity {
+                    entity: Entity::from_raw_u32(2).unwrap(),
+                    components: vec![Box::new(Foo), Box::new(Baz)],
+                },
+            ],
+        };
+
+        let mut app = app();
+        app.register_type::<Bar>()
+            .register_type::<Baz>()
+            .add_observer(load_on_default_event);
+
+        let world = app.world_mut();
+        world.trigger_load(
+            LoadWorld::<DefaultUnloadFilter>::new(LoadInput::Scene(scene), SceneMapper::default())
+                .include_entities_with::<Bar>()
+                .exclude_component::<Baz>(),
+        );
+        world.flush();
+
+        assert!(world
+            .query_filtered::<(), (With<Foo>, With<Bar>)>()
+            .single(world)
+            .is_ok());
+        assert_eq!(world.query::<&Baz>().iter(world).count(), 0);
+    }
+
+    #[test]
+    fn test_load_missing_resources() {
+        #[derive(Resource, Default, Reflect)]
+        #[reflect(Resource, Default)]
+        struct Bar(u32);
+
+        #[derive(Resource, Default, Reflect)]
+        #[reflect(Resource, Default)]
+        struct Baz(u32);
+
+        let mut app = app();
+        app.register_type::<Bar>()
+            .register_type::<Baz>()
+            .add_observer(load_on_default_event);
+
+        let world = app.world_mut();
+        world.insert_resource(Bar(1));
+        world.insert_resource(Baz(1));
+        world.trigger_load(
+            LoadWorld::<DefaultUnloadFilter>::new(
+                LoadInput::Scene(DynamicScene::default()),
+                SceneMapper::default(),
+            )
+            .include_resource::<Bar>()
+            .on_missing_resources(MissingResourcePolicy::Reset),
+        );
+        world.flush();
+
+        assert_eq!(world.resource::<Bar>().0, 0);
+        assert_eq!(world.resource::<Baz>().0, 1);
+
+        world.trigger_load(
+            LoadWorld::<DefaultUnloadFilter>::new(
+                LoadInput::Scene(DynamicScene::default()),
+                SceneMapper::default(),
+            )
+            .include_resource::<Bar>()
+            .on_missing_resources(MissingResourcePolicy::Remove),
+        );
+        world.flush();
+
+        assert!(!world.contains_resource::<Bar>());
+        assert!(world.contains_resource::<Baz>());
+
+        world.trigger_load(
+            LoadWorld::<DefaultUnloadFilter>::new(
+                LoadInput::Scene(DynamicScene::default()),
+                SceneMapper::default(),
+            )
+            .on_missing_resources(MissingResourcePolicy::Remove),
+        );
+        world.flush();
+
+        assert!(world.contains_resource::<Baz>());
+    }
+
+    #[test]
+    fn test_unload_policy() {
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component, Default)]
+        struct Bar(u32);
+
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = app();
+        app.register_type::<Bar>()
+            .add_observer(load_on_default_event);
+
+        let world = app.world_mut();
+        let a = world.spawn((Unload, UnloadPolicy::Keep, Bar(1))).id();
+        let b = world
+            .spawn((
+                Unload,
+                UnloadPolicy::Reset(SceneFilter::deny_all().allow::<Bar>()),
+                Bar(1),
+            ))
+            .id();
+        let c = world
+            .spawn((
+                Unload,
+                UnloadPolicy::custom(|mut entity| {
+                    entity.insert(Baz);
+                }),
+            ))
+            .id();
+        let d = world.spawn((Unload, UnloadPolicy::Despawn)).id();
+        world.trigger_load(LoadWorld::<DefaultUnloadFilter>::new(
+            LoadInput::Scene(DynamicScene::default()),
+            SceneMapper::default(),
+        ));
+        world.flush();
+
+        assert_eq!(world.get::<Bar>(a).unwrap().0, 1);
+        assert_eq!(world.get::<Bar>(b).unwrap().0, 0);
+        assert!(world.entity(c).contains::<Baz>());
+        assert!(world.get_entity(d).is_err());
+    }
+
+    #[test]
+    fn test_keep_on_load() {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let world = app.world_mut();
+        let a = world.spawn((Save, KeepOnLoad)).id();
+        let b = world.spawn((Unload, ChildOf(a))).id();
+        let c = world.spawn(Unload).id();
+        let d = world.spawn((Save, KeepOnLoad, ChildOf(c))).id();
+        world.trigger_load(LoadWorld::<DefaultUnloadFilter>::new(
+            LoadInput::Scene(DynamicScene::default()),
+            SceneMapper::default(),
+        ));
+        world.flush();
+
+        assert!(world.get_entity(a).is_ok());
+        assert!(world.get_entity(b).is_ok());
+        assert!(world.get_entity(c).is_err());
+        assert!(world.get_entity(d).is_ok());
+        assert!(!world.entity(d).contains::<ChildOf>());
+    }
+
+    #[test]
+    fn test_keep_on_load_round_trip() {
+        use crate::save::{save_on_default_event, SaveWorld, TriggerSave};
+
+        pub const PATH: &str = "test_keep_on_load_round_trip.ron";
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component)]
+        struct Rig(u32);
+
+        #[derive(Component, Reflect, Clone, PartialEq, Eq, Hash)]
+        #[reflect(Component)]
+        struct RigId(u32);
+
+        let app = || {
+            let mut app = app();
+            app.register_type::<Rig>()
+                .register_type::<RigId>()
+                .add_observer(save_on_default_event)
+                .add_observer(load_on_default_event);
+            app
+        };
+
+        let mut saved = app();
+        let world = saved.world_mut();
+        let a = world.spawn((Foo, Rig(1), RigId(1), KeepOnLoad)).id();
+        world.spawn((Foo, ChildOf(a)));
+        world.trigger_save(SaveWorld::default_into_file(PATH));
+        world.flush();
+
+        // Entity IDs differ from the saved data, and an unrelated kept entity reuses the saved ID
+        let mut loaded = app();
+        let world = loaded.world_mut();
+        let x = world.spawn((Foo, Rig(5), KeepOnLoad)).id();
+        let y = world.spawn((Foo, Rig(2), RigId(1), KeepOnLoad)).id();
+        assert_eq!(x, a);
+
+        world.trigger_load(LoadWorld::default_from_file(PATH).match_kept_by::<RigId>());
+        world.flush();
+
+        assert_eq!(world.query::<&Rig>().iter(world).count(), 2);
+        assert_eq!(world.query::<&Foo>().iter(world).count(), 3);
+        assert_eq!(world.get::<Rig>(x).unwrap().0, 5);
+        assert_eq!(world.get::<Rig>(y).unwrap().0, 1);
+        assert_eq!(world.get::<Children>(y).unwrap().len(), 1);
+        assert!(world.get::<Children>(x).is_none());
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_load_marker() {
+        use crate::save::{save_on_default_event, SaveWorld, TriggerSave};
+
+        pub const PATH: &str = "test_load_marker.ron";
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component)]
+        struct Unmarked;
+
+        let mut app = app();
+        app.register_type::<Unmarked>()
+            .add_observer(save_on_default_event)
+            .add_observer(load_on_default_event);
+
+        let world = app.world_mut();
+        world.spawn((Unmarked, Save));
+        world.trigger_save(SaveWorld::default_into_file(PATH));
+        world.flush();
+
+        world.trigger_load(LoadWorld::default_from_file(PATH));
+        world.flush();
+        let entity = world
+            .query_filtered::<Entity, With<Unmarked>>()
+            .single(world)
+            .unwrap();
+        assert!(world.entity(entity).contains::<Save>());
+
+        world.trigger_load(LoadWorld::default_from_file(PATH).without_marker());
+        world.flush();
+        let entity = world
+            .query_filtered::<Entity, With<Unmarked>>()
+            .single(world)
+            .unwrap();
+        assert!(!world.entity(entity).contains::<Save>());
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_load_merge() {
+        use crate::save::{save_on_default_event, SaveWorld, TriggerSave};
+
+        pub const PATH: &str = "test_load_merge.ron";
+
+        #[derive(Component, Reflect, Clone, PartialEq, Eq, Hash)]
+        #[reflect(Component)]
+        #[require(Save)]
+        struct Id(u32);
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component)]
+        struct Bar(u32);
+
+        let mut app = app();
+        app.register_type::<Id>()
+            .register_type::<Bar>()
+            .add_observer(save_on_default_event)
+            .add_observer(load_on_default_event);
+
+        let a = app
+            .world_mut()
+            .run_system_once(|mut commands: Commands| {
+                let a = commands.spawn((Id(1), Bar(1))).id();
+                commands.trigger_save(SaveWorld::default_into_file(PATH));
+                a
+            })
+            .unwrap();
+
+        let b = app
+            .world_mut()
+            .run_system_once(move |mut commands: Commands| {
+                commands.entity(a).insert(Bar(2));
+                let b = commands.spawn((Id(2), Bar(3))).id();
+                commands.trigger_load(
+                    LoadWorld::default_from_file(PATH)
+                        .merge(MergeBy::new::<Id>().despawn_missing()),
+                );
+                b
+            })
+            .unwrap();
+
+        let world = app.world_mut();
+        assert_eq!(world.get::<Bar>(a).unwrap().0, 1);
+        assert!(world.get_entity(b).is_err());
+        assert_eq!(world.query::<&Id>().iter(world).count(), 1);
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_load_merge_by() {
+        use crate::save::{save_on_default_event, SaveWorld, TriggerSave};
+
+        pub const PATH: &str = "test_load_merge_by.ron";
+
+        #[derive(Component, Reflect, Clone, PartialEq, Eq, Hash)]
+        #[reflect(Component)]
+        #[require(Save)]
+        struct Tag(u32);
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component)]
+        struct Qux(u32);
+
+        let mut app = app();
+        app.register_type::<Tag>()
+            .register_type::<Qux>()
+            .add_observer(save_on_default_event)
+            .add_observer(load_on_default_event);
+
+        let world = app.world_mut();
+        let a = world.spawn((Tag(1), Qux(1))).id();
+        let b = world.spawn((Tag(2), Qux(2))).id();
+        world.trigger_save(SaveWorld::default_into_file(PATH));
+        world.flush();
+
+        // Missing IDs are kept without `despawn_missing`
+        world.entity_mut(a).insert(Qux(10));
+        world.despawn(b);
+        let c = world.spawn((Tag(3), Qux(3))).id();
+        world.trigger_load(LoadWorld::default_from_file(PATH).merge_by::<Tag>());
+        world.flush();
+
+        assert_eq!(world.get::<Qux>(a).unwrap().0, 1);
+        assert_eq!(world.get::<Qux>(c).unwrap().0, 3);
+        assert_eq!(world.query::<&Tag>().iter(world).count(), 3);
+
+        // Missing IDs are despawned with `despawn_missing`
+        world.trigger_load(
+            LoadWorld::default_from_file(PATH).merge(MergeBy::new::<Tag>().despawn_missing()),
+        );
+        world.flush();
+
+        assert!(world.get_entity(c).is_err());
+        assert_eq!(world.query::<&Tag>().iter(world).count(), 2);
+
+        // Duplicate IDs are never merged
+        let d = world.spawn((Tag(1), Qux(4))).id();
+        world.entity_mut(a).insert(Qux(10));
+        world.trigger_load(LoadWorld::default_from_file(PATH).merge_by::<Tag>());
+        world.flush();
+
+        assert_eq!(world.get::<Qux>(a).unwrap().0, 10);
+        assert_eq!(world.get::<Qux>(d).unwrap().0, 4);
+        assert_eq!(world.query::<&Tag>().iter(world).count(), 4);
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_load_merge_with_anchor() {
+        use crate::save::{save_on_default_event, SaveWorld, TriggerSave};
+
+        pub const PATH: &str = "test_load_merge_with_anchor.ron";
+
+        #[derive(Component, Reflect, Clone, PartialEq, Eq, Hash)]
+        #[reflect(Component)]
+        #[require(Save)]
+        struct Key(u32);
+
+        let mut app = app();
+        app.register_type::<Key>()
+            .add_observer(save_on_default_event)
+            .add_observer(load_on_default_event);
+
+        let (a, b) = app
+            .world_mut()
+            .run_system_once(|mut commands: Commands| {
+                let a = commands.spawn(Key(1)).id();
+                let b = commands.spawn(Key(2)).id();
+                commands.trigger_save(SaveWorld::default_into_file(PATH));
+                (a, b)
+            })
+            .unwrap();
+
+        app.world_mut().despawn(b);
+
+        let anchor = app
+            .world_mut()
+            .run_system_once(move |mut commands: Commands| {
+                let anchor = commands.spawn_empty().id();
+                commands.trigger_load(
+                    LoadWorld::default_from_file(PATH)
+                        .merge(MergeBy::new::<Key>())
+                        .with_anchor(anchor),
+                );
+                anchor
+            })
+            .unwrap();
+
+        let world = app.world_mut();
+        assert!(world.get::<ChildOf>(a).is_none());
+        let children = world.get::<Children>(anchor).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(world.get::<Key>(children[0]).unwrap().0, 2);
+
+        remove_file(PATH).unwrap();
+    }
 }
@@ -1,23 +1,254 @@
-use std::any::TypeId;
+use std::any::{type_name, TypeId};
+use std::fmt;
 use std::io::{self, Write};
 use std::marker::PhantomData;
 use std::path::PathBuf;
 
-use bevy_ecs::entity::EntityHashSet;
+use bevy_app::App;
+use bevy_ecs::component::ComponentId;
+use bevy_ecs::entity::{EntityHashSet, EntityMapper};
+use bevy_ecs::lifecycle::HookContext;
 use bevy_ecs::prelude::*;
 use bevy_ecs::query::QueryFilter;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_ecs::relationship::RelationshipAccessor;
+use bevy_ecs::world::DeferredWorld;
 use bevy_log::prelude::*;
-use bevy_scene::{DynamicScene, DynamicSceneBuilder, SceneFilter};
+use bevy_platform::collections::HashSet;
+use bevy_reflect::serde::{
+    ReflectSerializeWithRegistry, SerializationData, TypedReflectSerializer,
+};
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::{
+    NamedField, PartialReflect, Reflect, ReflectFromReflect, ReflectRef, ReflectSerialize,
+    TypeInfo, TypeRegistry,
+};
+use bevy_scene::serde::{
+    ENTITY_FIELD_COMPONENTS, ENTITY_STRUCT, SCENE_ENTITIES, SCENE_RESOURCES, SCENE_STRUCT,
+};
+use bevy_scene::{serialize_ron, DynamicEntity, DynamicScene, DynamicSceneBuilder, SceneFilter};
 
 use moonshine_util::event::{OnSingle, SingleEvent, TriggerSingle};
 use moonshine_util::Static;
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
 use thiserror::Error;
 
-use crate::{MapComponent, SceneMapper};
+use crate::{
+    BundleInput, MapBundle, MapComponent, MapComponentWithContext, MapError, MapResource,
+    SceneMapper, TryMapComponent,
+};
 
 /// A [`Component`] which marks its [`Entity`] to be saved.
-#[derive(Component, Default, Debug, Clone)]
-pub struct Save;
+///
+/// # Domains
+///
+/// Each `Save` marker belongs to a save domain `D`, which is any `'static` marker type.
+/// Entities of different domains are saved and loaded independently of each other.
+/// This is useful to keep different parts of the game state (such as player profiles and level state)
+/// in separate save files.
+///
+/// By default, all entities belong to the default domain `()`.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// struct Profile;
+///
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component)]
+/// #[require(Save<Profile>)]
+/// struct PlayerName(String);
+///
+/// let mut app = App::new();
+/// app.register_type::<PlayerName>()
+///     .add_observer(save_on::<SaveWorld<DefaultSaveFilter<Profile>>>)
+///     .add_observer(load_on::<LoadWorld<DefaultUnloadFilter<Profile>>>);
+///
+/// fn save_profile(mut commands: Commands) {
+///     commands.trigger_save(SaveWorld::domain_into_file::<Profile>("profile.ron"));
+/// }
+/// ```
+#[derive(Component)]
+pub struct Save<D: Static = ()> {
+    domain: PhantomData<D>,
+}
+
+impl<D: Static> Save<D> {
+    /// Creates a new [`Save`] marker for the domain `D`.
+    pub const fn new() -> Self {
+        Self {
+            domain: PhantomData,
+        }
+    }
+}
+
+impl<D: Static> Default for Save<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Static> Clone for Save<D> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Static> fmt::Debug for Save<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Save<{}>", type_name::<D>())
+    }
+}
+
+/// The [`Save`] marker of the default domain.
+///
+/// This allows the marker to be used as a value, such as `commands.spawn(Save)`.
+#[allow(non_upper_case_globals)]
+pub const Save: Save = Save::new();
+
+/// A [`Component`] which excludes the component `T` of its [`Entity`] from being saved.
+///
+/// Unlike [`SaveWorld::exclude_component`], which excludes a component from all saved entities,
+/// this only excludes the component from this entity. This applies to all save events.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component)]
+/// struct PathCache(Vec<Vec3>);
+///
+/// fn spawn_debug_unit(mut commands: Commands) {
+///     commands.spawn((Save, PathCache::default(), DontSave::<PathCache>::default()));
+/// }
+/// ```
+#[derive(Component)]
+#[component(on_add = Self::on_add, on_remove = Self::on_remove)]
+#[require(ExcludedComponents)]
+pub struct DontSave<T: Component>(PhantomData<T>);
+
+impl<T: Component> DontSave<T> {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        if let Some(mut excluded) = world.get_mut::<ExcludedComponents>(ctx.entity) {
+            excluded.0.insert(TypeId::of::<T>());
+        }
+    }
+
+    fn on_remove(mut world: DeferredWorld, ctx: HookContext) {
+        if let Some(mut excluded) = world.get_mut::<ExcludedComponents>(ctx.entity) {
+            excluded.0.remove(&TypeId::of::<T>());
+        }
+    }
+}
+
+impl<T: Component> Default for DontSave<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Set of components excluded from being saved on an entity. See [`DontSave`].
+#[derive(Component, Default)]
+struct ExcludedComponents(HashSet<TypeId>);
+
+/// An extension trait for [`App`] used to register transient types.
+///
+/// Transient components and resources are excluded from every save operation, regardless of
+/// the [`SceneFilter`] returned by [`SaveEvent::component_filter`] or [`SaveEvent::resource_filter`].
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component)]
+/// struct PathCache(Vec<Vec3>);
+///
+/// let mut app = App::new();
+/// app.register_transient::<PathCache>();
+/// ```
+pub trait RegisterTransient {
+    /// Excludes the component `T` from all saved entities.
+    fn register_transient<T: Component>(&mut self) -> &mut Self;
+
+    /// Excludes the resource `R` from all saved data.
+    fn register_transient_resource<R: Resource>(&mut self) -> &mut Self;
+}
+
+impl RegisterTransient for App {
+    fn register_transient<T: Component>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<TransientTypes>()
+            .components
+            .insert(TypeId::of::<T>());
+        self
+    }
+
+    fn register_transient_resource<R: Resource>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<TransientTypes>()
+            .resources
+            .insert(TypeId::of::<R>());
+        self
+    }
+}
+
+/// Set of components and resources excluded from all save operations. See [`RegisterTransient`].
+#[derive(Resource, Default)]
+struct TransientTypes {
+    components: HashSet<TypeId>,
+    resources: HashSet<TypeId>,
+}
+
+/// A custom reflect attribute which excludes a field from being saved.
+///
+/// Skipped fields are omitted from the saved data and filled from their default values when loaded.
+/// This requires the component (or resource) to either reflect [`Default`] or mark the skipped field
+/// with `#[reflect(default)]`.
+///
+/// Only named fields of structs may be skipped. This attribute has no effect on fields of tuple structs.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component, Default)]
+/// struct Unit {
+///     health: u32,
+///     #[reflect(@SaveSkip)]
+///     path: Vec<Vec3>,
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Debug, Default)]
+pub struct SaveSkip;
+
+/// A custom reflect attribute which allows a component to be pruned from saved data.
+///
+/// When saved with [`SaveWorld::prune_required_defaults`], a component marked with this attribute is omitted
+/// if it is required by another saved component and equal to its [`Default`] value.
+///
+/// Only mark components which are always required with their [`Default`] value, such as `#[require(Velocity)]`.
+/// A component required with a custom constructor, such as `#[require(Health = Health(100))]`,
+/// would be recreated with a different value when loaded.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component, Default, @SavePrune)]
+/// struct Velocity(Vec3);
+/// ```
+#[derive(Reflect, Clone, Copy, Debug, Default)]
+pub struct SavePrune;
 
 /// A trait used to trigger a [`SaveEvent`] via [`Commands`] or [`World`].
 pub trait TriggerSave {
@@ -58,11 +289,24 @@ pub trait SaveEvent: SingleEvent {
     /// This is useful if you want to modify the world just before saving.
     fn before_save(&mut self, _world: &mut World) {}
 
+    /// Called once before saved entities are selected, after [`SaveEvent::before_save`].
+    ///
+    /// Unlike [`SaveEvent::before_save`], this is also called by [`save_readonly`].
+    fn prepare(&mut self, _world: &World) {}
+
     /// Called once before serialization.
     ///
     /// This is useful to undo any modifications done before saving.
     fn before_serialize(&mut self, _world: &mut World, _entities: &[Entity]) {}
 
+    /// Returns `true` if this event must modify the world to be saved correctly.
+    ///
+    /// If so, [`save_readonly`] fails with [`SaveError::ReadOnly`] instead of saving without the modifications.
+    /// You should override this if you modify the world in [`SaveEvent::before_save`] or [`SaveEvent::before_serialize`].
+    fn requires_mutable_world(&self) -> bool {
+        false
+    }
+
     /// Returns a [`SceneFilter`] for selecting which components should be saved.
     fn component_filter(&mut self) -> SceneFilter {
         SceneFilter::allow_all()
@@ -73,11 +317,31 @@ pub trait SaveEvent: SingleEvent {
         SceneFilter::deny_all()
     }
 
+    /// Returns `true` if struct fields equal to their [`Default`] value should be omitted from the saved data.
+    ///
+    /// See [`SaveWorld::elide_defaults`] for more information.
+    fn elide_defaults(&self) -> bool {
+        false
+    }
+
+    /// Called once after the [`DynamicScene`] is extracted, before it is written into [`SaveOutput`].
+    ///
+    /// This is useful if you want to modify the saved data without modifying the world.
+    fn process_scene(&mut self, _world: &World, _scene: &mut DynamicScene) {}
+
     /// Called once after serialization.
     ///
     /// This is useful if you would like to do any post-processing of the [`Saved`] data *before* [`OnSave`] is triggered.
+    /// This is also called if the save process fails.
     fn after_save(&mut self, _world: &mut World, _result: &SaveResult) {}
 
+    /// Returns an error which occurred during [`SaveEvent::before_serialize`], if any.
+    ///
+    /// If an error is returned, the save process is aborted.
+    fn take_error(&mut self) -> Option<SaveError> {
+        None
+    }
+
     /// Returns the [`SaveOutput`] of the save process.
     fn output(&mut self) -> SaveOutput;
 }
@@ -102,6 +366,14 @@ pub struct SaveWorld<F: QueryFilter = DefaultSaveFilter> {
     pub mapper: SceneMapper,
     /// Output of the saved world.
     pub output: SaveOutput,
+    /// If true, required components which are equal to their default value are not saved.
+    ///
+    /// See [`SaveWorld::prune_required_defaults`] for more information.
+    pub prune_required: bool,
+    /// If true, struct fields which are equal to their default value are not saved.
+    ///
+    /// See [`SaveWorld::elide_defaults`] for more information.
+    pub elide_defaults: bool,
     #[doc(hidden)]
     pub filter: PhantomData<F>,
 }
@@ -115,6 +387,8 @@ impl<F: QueryFilter> SaveWorld<F> {
             components: SceneFilter::allow_all(),
             mapper: SceneMapper::default(),
             output,
+            prune_required: false,
+            elide_defaults: false,
             filter: PhantomData,
         }
     }
@@ -128,6 +402,8 @@ impl<F: QueryFilter> SaveWorld<F> {
             components: SceneFilter::allow_all(),
             mapper: SceneMapper::default(),
             output: SaveOutput::file(path),
+            prune_required: false,
+            elide_defaults: false,
             filter: PhantomData,
         }
     }
@@ -141,6 +417,8 @@ impl<F: QueryFilter> SaveWorld<F> {
             components: SceneFilter::allow_all(),
             mapper: SceneMapper::default(),
             output: SaveOutput::stream(stream),
+            prune_required: false,
+            elide_defaults: false,
             filter: PhantomData,
         }
     }
@@ -174,6 +452,70 @@ impl<F: QueryFilter> SaveWorld<F> {
         self.mapper = self.mapper.map(m);
         self
     }
+
+    /// Maps the given components into another [`Bundle`] using a [bundle mapper](MapBundle) before saving.
+    pub fn map_bundle<B: BundleInput>(mut self, m: impl MapBundle<B>) -> Self {
+        self.mapper = self.mapper.map_bundle(m);
+        self
+    }
+
+    /// Maps the given [`Resource`] into another using a [resource mapper](MapResource) before saving.
+    ///
+    /// The mapped output is included in the save data.
+    pub fn map_resource<R: Resource, M: MapResource<R>>(mut self, m: M) -> Self {
+        self.resources = self.resources.allow::<M::Output>();
+        self.mapper = self.mapper.map_resource(m);
+        self
+    }
+
+    /// Maps the given [`Component`] into another using a [fallible component mapper](TryMapComponent) before saving.
+    pub fn try_map_component<T: Component>(mut self, m: impl TryMapComponent<T>) -> Self {
+        self.mapper = self.mapper.try_map(m);
+        self
+    }
+
+    /// Maps the given [`Component`] into another using a [component mapper with context](MapComponentWithContext)
+    /// before saving.
+    pub fn map_component_with_context<T: Component>(
+        mut self,
+        m: impl MapComponentWithContext<T>,
+    ) -> Self {
+        self.mapper = self.mapper.map_with_context(m);
+        self
+    }
+
+    /// Maps the given [`Component`] into another directly in the saved data, without modifying the world.
+    ///
+    /// See [`SceneMapper::map_in_scene`] for more information.
+    pub fn map_component_in_scene<T: Component, M: MapComponent<T>>(mut self, m: M) -> Self
+    where
+        M::Output: Reflect,
+    {
+        self.mapper = self.mapper.map_in_scene(m);
+        self
+    }
+
+    /// Omits components marked with [`SavePrune`] which are required by another saved component
+    /// and equal to their default value.
+    ///
+    /// These components are recreated by their [required components](Component#required-components)
+    /// when the saved data is loaded. Components which are not marked with [`SavePrune`], or do not
+    /// reflect [`Default`], are always saved.
+    pub fn prune_required_defaults(mut self) -> Self {
+        self.prune_required = true;
+        self
+    }
+
+    /// Omits struct fields which are equal to their value in the [`Default`] value of their component or resource.
+    ///
+    /// A component or resource which is equal to its default value is saved without any fields.
+    /// Omitted fields are restored from the default value when loaded.
+    ///
+    /// Only types which reflect [`Default`] and are not serialized using [`serde`] are affected.
+    pub fn elide_defaults(mut self) -> Self {
+        self.elide_defaults = true;
+        self
+    }
 }
 
 impl SaveWorld {
@@ -188,6 +530,26 @@ impl SaveWorld {
     pub fn default_into_stream(stream: impl SaveStream) -> Self {
         Self::into_stream(stream)
     }
+
+    /// Creates a new [`SaveWorld`] event which saves entities of the given domain (with [`Save<D>`])
+    /// into a file at the given path.
+    ///
+    /// See [`Save`] for more information about domains.
+    pub fn domain_into_file<D: Static>(
+        path: impl Into<PathBuf>,
+    ) -> SaveWorld<DefaultSaveFilter<D>> {
+        SaveWorld::into_file(path)
+    }
+
+    /// Creates a new [`SaveWorld`] event which saves entities of the given domain (with [`Save<D>`])
+    /// into a [`Write`] stream.
+    ///
+    /// See [`Save`] for more information about domains.
+    pub fn domain_into_stream<D: Static>(
+        stream: impl SaveStream,
+    ) -> SaveWorld<DefaultSaveFilter<D>> {
+        SaveWorld::into_stream(stream)
+    }
 }
 
 impl SaveWorld<()> {
@@ -200,6 +562,34 @@ impl SaveWorld<()> {
     pub fn all_into_stream(stream: impl SaveStream) -> Self {
         Self::into_stream(stream)
     }
+
+    /// Creates a new [`SaveWorld`] event which saves the given root entities and all
+    /// their related entities into a file at the given path.
+    ///
+    /// See [`EntityFilter::Subtree`] for more information.
+    pub fn subtree_into_file(
+        roots: impl IntoIterator<Item = Entity>,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            entities: EntityFilter::subtree(roots),
+            ..Self::into_file(path)
+        }
+    }
+
+    /// Creates a new [`SaveWorld`] event which saves the given root entities and all
+    /// their related entities into a [`Write`] stream.
+    ///
+    /// See [`EntityFilter::Subtree`] for more information.
+    pub fn subtree_into_stream(
+        roots: impl IntoIterator<Item = Entity>,
+        stream: impl SaveStream,
+    ) -> Self {
+        Self {
+            entities: EntityFilter::subtree(roots),
+            ..Self::into_stream(stream)
+        }
+    }
 }
 
 impl<F: QueryFilter> SingleEvent for SaveWorld<F> where F: Static {}
@@ -214,23 +604,35 @@ where
         match &self.entities {
             EntityFilter::Allow(allow) => allow.contains(&entity.id()),
             EntityFilter::Block(block) => !block.contains(&entity.id()),
+            EntityFilter::Subtree(subtree) => subtree.contains(&entity.id()),
+        }
+    }
+
+    fn prepare(&mut self, world: &World) {
+        if let EntityFilter::Subtree(roots) = &mut self.entities {
+            *roots = collect_subtree(world, roots.iter().copied());
         }
     }
 
     fn before_serialize(&mut self, world: &mut World, entities: &[Entity]) {
+        self.mapper.add_save_mappings(world);
         for entity in entities {
             self.mapper.apply(world.entity_mut(*entity));
         }
+        self.mapper.apply_resources(world);
     }
 
-    fn after_save(&mut self, world: &mut World, result: &SaveResult) {
-        let Ok(saved) = result else {
-            return;
-        };
+    fn requires_mutable_world(&self) -> bool {
+        self.mapper.modifies_world()
+    }
 
-        for entity in saved.entities() {
-            self.mapper.undo(world.entity_mut(entity));
-        }
+    fn after_save(&mut self, world: &mut World, _result: &SaveResult) {
+        self.mapper.undo(world);
+        self.mapper.undo_resources(world);
+    }
+
+    fn take_error(&mut self) -> Option<SaveError> {
+        self.mapper.take_error().map(SaveError::Map)
     }
 
     fn component_filter(&mut self) -> SceneFilter {
@@ -241,14 +643,30 @@ where
         std::mem::replace(&mut self.resources, SceneFilter::Unset)
     }
 
+    fn process_scene(&mut self, world: &World, scene: &mut DynamicScene) {
+        self.mapper.apply_scene(world, scene);
+
+        if let EntityFilter::Subtree(subtree) = &self.entities {
+            remove_references(world, scene, |entity| !subtree.contains(&entity));
+        }
+
+        if self.prune_required {
+            prune_required_defaults(world, scene);
+        }
+    }
+
+    fn elide_defaults(&self) -> bool {
+        self.elide_defaults
+    }
+
     fn output(&mut self) -> SaveOutput {
         self.output.consume().unwrap()
     }
 }
 
 /// Filter used for the default [`SaveWorld`] event.
-/// This includes all entities with the [`Save`] component.
-pub type DefaultSaveFilter = With<Save>;
+/// This includes all entities with the [`Save`] component of the given domain.
+pub type DefaultSaveFilter<D = ()> = With<Save<D>>;
 
 /// Output of the save process.
 pub enum SaveOutput {
@@ -293,6 +711,19 @@ pub enum EntityFilter {
     Allow(EntityHashSet),
     /// Select all entities except the specified ones.
     Block(EntityHashSet),
+    /// Select the specified root entities and all of their related entities.
+    ///
+    /// Before the save process starts, this set is expanded to include all entities
+    /// reachable from the roots through [`Children`] or any other [`RelationshipTarget`].
+    ///
+    /// Any saved component which references an entity outside of this set is dropped.
+    /// This includes the [`ChildOf`] component of the roots, which allows the saved data
+    /// to be loaded independently of its original parent.
+    /// A warning is reported for all dropped components which are not a [`Relationship`].
+    ///
+    /// [`RelationshipTarget`]: bevy_ecs::relationship::RelationshipTarget
+    /// [`Relationship`]: bevy_ecs::relationship::Relationship
+    Subtree(EntityHashSet),
 }
 
 impl EntityFilter {
@@ -310,6 +741,13 @@ impl EntityFilter {
     pub fn block(entities: impl IntoIterator<Item = Entity>) -> Self {
         Self::Block(entities.into_iter().collect())
     }
+
+    /// Creates a new [`EntityFilter`] which selects the specified root entities and all of their related entities.
+    ///
+    /// See [`EntityFilter::Subtree`] for more information.
+    pub fn subtree(roots: impl IntoIterator<Item = Entity>) -> Self {
+        Self::Subtree(roots.into_iter().collect())
+    }
 }
 
 impl Default for EntityFilter {
@@ -356,6 +794,14 @@ pub enum SaveError {
     /// An error occurred while writing into [`SaveOutput`].
     #[error("Failed to write world: {0}")]
     Io(io::Error),
+    /// An error returned by a [fallible component mapper](TryMapComponent).
+    #[error("Failed to map component: {0}")]
+    Map(MapError),
+    /// The world must be modified to be saved correctly, but only shared access is available.
+    ///
+    /// See [`save_readonly`] for more information.
+    #[error("Failed to save world with shared access: {0}")]
+    ReadOnly(&'static str),
 }
 
 impl From<ron::Error> for SaveError {
@@ -385,9 +831,74 @@ pub fn save_on<E: SaveEvent>(event: OnSingle<E>, mut commands: Commands) {
     });
 }
 
+/// Saves the world using the given [`SaveEvent`] with only shared access to the [`World`].
+///
+/// Unlike [`save_on`], this may be called from a regular system with a `&World` parameter, and may run
+/// in parallel with other read-only systems.
+///
+/// Because the world may not be modified, [`SaveEvent::before_save`], [`SaveEvent::before_serialize`] and
+/// [`SaveEvent::after_save`] are not called and no [`Saved`] event is triggered.
+///
+/// Instead of saving incomplete data, this fails with [`SaveError::ReadOnly`] if:
+/// - [`SaveEvent::requires_mutable_world`] returns `true`. For [`SaveWorld`], this is the case if it has
+///   any component, bundle or resource mappers (such as [`SaveWorld::map_component`]).
+///   Use [`SaveWorld::map_component_in_scene`] instead.
+/// - Any save mappings are registered using [`RegisterSaveMapping`](crate::RegisterSaveMapping).
+/// - Any serde components are registered using [`RegisterSerdeComponent`](crate::RegisterSerdeComponent),
+///   even if no saved entity has them.
+/// - Any saved entity has a component with [`SaveHooks`](crate::SaveHooks).
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_save::prelude::*;
+///
+/// fn autosave(world: &World) {
+///     if let Err(error) = save_readonly(SaveWorld::default_into_file("autosave.ron"), world) {
+///         error!("autosave failed: {error:?}");
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_systems(Update, autosave);
+/// ```
+pub fn save_readonly<E: SaveEvent>(mut event: E, world: &World) -> SaveResult {
+    // Notify
+    event.prepare(world);
+
+    // Filter
+    let entities: Vec<_> = world
+        .try_query_filtered::<Entity, E::SaveFilter>()
+        .map(|mut query| {
+            query
+                .iter(world)
+                .filter(|entity| event.filter_entity(world.entity(*entity)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if event.requires_mutable_world() {
+        return Err(SaveError::ReadOnly("save event modifies the world"));
+    }
+
+    if crate::has_save_mappings(world) {
+        return Err(SaveError::ReadOnly(
+            "save mappings or serde components are registered",
+        ));
+    }
+
+    if crate::has_save_hooks(world, &entities) {
+        return Err(SaveError::ReadOnly("saved entities have save hooks"));
+    }
+
+    // Serialize
+    extract_and_write(&mut event, world, &entities)
+}
+
 fn save_world<E: SaveEvent>(mut event: E, world: &mut World) -> SaveResult {
     // Notify
     event.before_save(world);
+    event.prepare(world);
 
     // Filter
     let entities: Vec<_> = world
@@ -397,13 +908,40 @@ fn save_world<E: SaveEvent>(mut event: E, world: &mut World) -> SaveResult {
         .collect();
 
     // Serialize
+    crate::prepare_for_save(world, &entities);
     event.before_serialize(world, &entities);
-    let scene = DynamicSceneBuilder::from_world(world)
-        .with_component_filter(event.component_filter())
-        .with_resource_filter(event.resource_filter())
+    let result = match event.take_error() {
+        Some(error) => Err(error),
+        None => extract_and_write(&mut event, world, &entities),
+    };
+
+    event.after_save(world, &result);
+    result
+}
+
+fn extract_and_write<E: SaveEvent>(
+    event: &mut E,
+    world: &World,
+    entities: &[Entity],
+) -> SaveResult {
+    let mut component_filter = event.component_filter();
+    let mut resource_filter = event.resource_filter();
+    if let Some(transient) = world.get_resource::<TransientTypes>() {
+        for &type_id in &transient.components {
+            component_filter = component_filter.deny_by_id(type_id);
+        }
+        for &type_id in &transient.resources {
+            resource_filter = resource_filter.deny_by_id(type_id);
+        }
+    }
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        .with_component_filter(component_filter)
+        .with_resource_filter(resource_filter)
         .extract_resources()
         .extract_entities(entities.iter().copied())
         .build();
+    remove_excluded_components(world, &mut scene);
+    event.process_scene(world, &mut scene);
 
     // Write
     let saved = match event.output() {
@@ -412,15 +950,13 @@ fn save_world<E: SaveEvent>(mut event: E, world: &mut World) -> SaveResult {
                 std::fs::create_dir_all(parent)?;
             }
 
-            let type_registry = world.resource::<AppTypeRegistry>().read();
-            let data = scene.serialize(&type_registry)?;
+            let data = serialize_scene(world, &scene, event.elide_defaults())?;
             std::fs::write(&path, data.as_bytes())?;
             debug!("saved into file: {path:?}");
             Saved { scene }
         }
         SaveOutput::Stream(mut stream) => {
-            let type_registry = world.resource::<AppTypeRegistry>().read();
-            let data = scene.serialize(&type_registry)?;
+            let data = serialize_scene(world, &scene, event.elide_defaults())?;
             stream.write_all(data.as_bytes())?;
             debug!("saved into stream");
             Saved { scene }
@@ -434,9 +970,439 @@ fn save_world<E: SaveEvent>(mut event: E, world: &mut World) -> SaveResult {
         }
     };
 
-    let result = Ok(saved);
-    event.after_save(world, &result);
-    result
+    Ok(saved)
+}
+
+/// Serializes the scene, omitting all fields marked with [`SaveSkip`].
+///
+/// If `elide_defaults` is set, fields which are equal to their default value are omitted as well.
+fn serialize_scene(
+    world: &World,
+    scene: &DynamicScene,
+    elide_defaults: bool,
+) -> Result<String, SaveError> {
+    let registry = &world.resource::<AppTypeRegistry>().read();
+    Ok(serialize_ron(SavedSceneSerializer {
+        scene,
+        registry,
+        elide_defaults,
+    })?)
+}
+
+/// Removes all components marked with [`SavePrune`] which are required by another component
+/// in the same scene entity and equal to their default value.
+fn prune_required_defaults(world: &World, scene: &mut DynamicScene) {
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let components = world.components();
+
+    for scene_entity in &mut scene.entities {
+        let ids: Vec<_> = scene_entity
+            .components
+            .iter()
+            .map(|component| {
+                component
+                    .get_represented_type_info()
+                    .and_then(|type_info| components.get_id(type_info.type_id()))
+            })
+            .collect();
+
+        let mut keep: Vec<bool> = scene_entity
+            .components
+            .iter()
+            .map(|component| {
+                !is_prunable(component.as_ref()) || !is_default(&type_registry, component.as_ref())
+            })
+            .collect();
+
+        // Default components must be kept unless they are required by a kept component.
+        loop {
+            let required: HashSet<ComponentId> = ids
+                .iter()
+                .zip(&keep)
+                .filter(|(_, keep)| **keep)
+                .filter_map(|(id, _)| id.and_then(|id| components.get_info(id)))
+                .flat_map(|info| info.required_components().iter_ids())
+                .collect();
+
+            let mut changed = false;
+            for (id, keep) in ids.iter().zip(&mut keep) {
+                if !*keep && !id.is_some_and(|id| required.contains(&id)) {
+                    *keep = true;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let mut keep = keep.into_iter();
+        scene_entity
+            .components
+            .retain(|_| keep.next().unwrap_or(true));
+    }
+}
+
+/// Returns true if the represented type of the given value is marked with [`SavePrune`].
+fn is_prunable(value: &dyn PartialReflect) -> bool {
+    match value.get_represented_type_info() {
+        Some(TypeInfo::Struct(info)) => info.custom_attributes().contains::<SavePrune>(),
+        Some(TypeInfo::TupleStruct(info)) => info.custom_attributes().contains::<SavePrune>(),
+        Some(TypeInfo::Enum(info)) => info.custom_attributes().contains::<SavePrune>(),
+        _ => false,
+    }
+}
+
+/// Returns true if the given value is equal to the [`Default`] value of its represented type.
+fn is_default(type_registry: &TypeRegistry, value: &dyn PartialReflect) -> bool {
+    value
+        .get_represented_type_info()
+        .and_then(|type_info| type_registry.get_type_data::<ReflectDefault>(type_info.type_id()))
+        .and_then(|reflect_default| {
+            reflect_default
+                .default()
+                .as_partial_reflect()
+                .reflect_partial_eq(value)
+        })
+        .unwrap_or(false)
+}
+
+/// Serializes a [`DynamicScene`] in the same format as [`SceneSerializer`](bevy_scene::serde::SceneSerializer),
+/// but omits struct fields which are marked with [`SaveSkip`] or, if `elide_defaults` is set,
+/// equal to their default value.
+struct SavedSceneSerializer<'a> {
+    scene: &'a DynamicScene,
+    registry: &'a TypeRegistry,
+    elide_defaults: bool,
+}
+
+impl Serialize for SavedSceneSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        state.serialize_field(
+            SCENE_RESOURCES,
+            &SavedMapSerializer {
+                entries: &self.scene.resources,
+                registry: self.registry,
+                elide_defaults: self.elide_defaults,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_ENTITIES,
+            &SavedEntitiesSerializer {
+                entities: &self.scene.entities,
+                registry: self.registry,
+                elide_defaults: self.elide_defaults,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct SavedEntitiesSerializer<'a> {
+    entities: &'a [DynamicEntity],
+    registry: &'a TypeRegistry,
+    elide_defaults: bool,
+}
+
+impl Serialize for SavedEntitiesSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_map(Some(self.entities.len()))?;
+        for entity in self.entities {
+            state.serialize_entry(
+                &entity.entity,
+                &SavedEntitySerializer {
+                    entity,
+                    registry: self.registry,
+                    elide_defaults: self.elide_defaults,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+struct SavedEntitySerializer<'a> {
+    entity: &'a DynamicEntity,
+    registry: &'a TypeRegistry,
+    elide_defaults: bool,
+}
+
+impl Serialize for SavedEntitySerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct(ENTITY_STRUCT, 1)?;
+        state.serialize_field(
+            ENTITY_FIELD_COMPONENTS,
+            &SavedMapSerializer {
+                entries: &self.entity.components,
+                registry: self.registry,
+                elide_defaults: self.elide_defaults,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct SavedMapSerializer<'a> {
+    entries: &'a [Box<dyn PartialReflect>],
+    registry: &'a TypeRegistry,
+    elide_defaults: bool,
+}
+
+impl Serialize for SavedMapSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let type_info = entry.get_represented_type_info()?;
+                Some((type_info.type_path(), entry.as_ref()))
+            })
+            .collect();
+        entries.sort_by_key(|(type_path, _)| *type_path);
+
+        let mut state = serializer.serialize_map(Some(entries.len()))?;
+        for (type_path, value) in entries {
+            state.serialize_entry(
+                type_path,
+                &SavedValueSerializer {
+                    value,
+                    registry: self.registry,
+                    elide_defaults: self.elide_defaults,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+struct SavedValueSerializer<'a> {
+    value: &'a dyn PartialReflect,
+    registry: &'a TypeRegistry,
+    elide_defaults: bool,
+}
+
+impl Serialize for SavedValueSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = self.value;
+        let registry = self.registry;
+
+        let saved = value
+            .get_represented_type_info()
+            .and_then(|type_info| registry.get(type_info.type_id()))
+            .filter(|registration| {
+                registration.data::<ReflectSerialize>().is_none()
+                    && registration
+                        .data::<ReflectSerializeWithRegistry>()
+                        .is_none()
+            })
+            .and_then(|registration| {
+                let TypeInfo::Struct(info) = registration.type_info() else {
+                    return None;
+                };
+                let ReflectRef::Struct(value) = value.reflect_ref() else {
+                    return None;
+                };
+                let default = registration
+                    .data::<ReflectDefault>()
+                    .filter(|_| self.elide_defaults)
+                    .map(|reflect_default| reflect_default.default());
+                let default = match default.as_ref().map(|default| default.reflect_ref()) {
+                    Some(ReflectRef::Struct(default)) => Some(default),
+                    _ => None,
+                };
+                let is_save_skipped =
+                    |field: &NamedField| field.custom_attributes().contains::<SaveSkip>();
+                if default.is_none() && !info.iter().any(is_save_skipped) {
+                    return None;
+                }
+                let ident = info.type_path_table().ident()?;
+                let skipped = registration.data::<SerializationData>();
+
+                let mut fields = Vec::new();
+                for (index, field) in info.iter().enumerate() {
+                    if is_save_skipped(field)
+                        || skipped.is_some_and(|data| data.is_field_skipped(index))
+                    {
+                        continue;
+                    }
+                    let value = value.field(field.name())?;
+                    let is_default = default
+                        .and_then(|default| default.field(field.name()))
+                        .and_then(|default| default.reflect_partial_eq(value))
+                        .unwrap_or(false);
+                    if !is_default {
+                        fields.push((field.name(), value));
+                    }
+                }
+                Some((ident, fields))
+            });
+
+        let Some((ident, fields)) = saved else {
+            return TypedReflectSerializer::new(value, registry).serialize(serializer);
+        };
+
+        let mut state = serializer.serialize_struct(ident, fields.len())?;
+        for (name, value) in fields {
+            state.serialize_field(name, &TypedReflectSerializer::new(value, registry))?;
+        }
+        state.end()
+    }
+}
+
+/// Removes all components excluded by [`DontSave`] from the scene.
+fn remove_excluded_components(world: &World, scene: &mut DynamicScene) {
+    for scene_entity in &mut scene.entities {
+        let Some(ExcludedComponents(excluded)) = world.get(scene_entity.entity) else {
+            continue;
+        };
+
+        scene_entity.components.retain(|component| {
+            component
+                .get_represented_type_info()
+                .is_none_or(|type_info| !excluded.contains(&type_info.type_id()))
+        });
+    }
+}
+
+/// Returns the given root entities and all entities reachable from them through a [`RelationshipTarget`].
+///
+/// [`RelationshipTarget`]: bevy_ecs::relationship::RelationshipTarget
+fn collect_subtree(world: &World, roots: impl IntoIterator<Item = Entity>) -> EntityHashSet {
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let mut subtree = EntityHashSet::new();
+    let mut queue: Vec<Entity> = roots.into_iter().collect();
+    while let Some(entity) = queue.pop() {
+        let Ok(entity) = world.get_entity(entity) else {
+            continue;
+        };
+
+        if !subtree.insert(entity.id()) {
+            continue;
+        }
+
+        for &component_id in entity.archetype().components().iter() {
+            let Some(info) = world.components().get_info(component_id) else {
+                continue;
+            };
+
+            if !matches!(
+                info.relationship_accessor(),
+                Some(RelationshipAccessor::RelationshipTarget { .. })
+            ) {
+                continue;
+            }
+
+            let Some(reflect_component) = info
+                .type_id()
+                .and_then(|type_id| type_registry.get_type_data::<ReflectComponent>(type_id))
+            else {
+                warn!(
+                    "relationship target {} is not registered and will not be traversed",
+                    info.name()
+                );
+                continue;
+            };
+
+            let Some(Ok(mut component)) = reflect_component
+                .reflect(entity)
+                .map(|component| component.reflect_clone())
+            else {
+                continue;
+            };
+
+            let mut collector = EntityCollector(Vec::new());
+            reflect_component.map_entities(component.as_mut(), &mut collector);
+            queue.extend(collector.0);
+        }
+    }
+    subtree
+}
+
+/// Removes all components from the given scene which reference any entity matching `is_excluded`.
+///
+/// Relationship components are removed silently, since the relationship is simply dropped.
+/// Any other component triggers a warning.
+/// Components which cannot be converted into their concrete type are kept unchecked, with a warning.
+pub(crate) fn remove_references(
+    world: &World,
+    scene: &mut DynamicScene,
+    is_excluded: impl Fn(Entity) -> bool,
+) {
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    for scene_entity in &mut scene.entities {
+        scene_entity.components.retain_mut(|component| {
+            let Some(type_id) = component
+                .get_represented_type_info()
+                .map(|type_info| type_info.type_id())
+            else {
+                return true;
+            };
+
+            let Some(reflect_component) = type_registry.get_type_data::<ReflectComponent>(type_id)
+            else {
+                return true;
+            };
+
+            let is_concrete = component
+                .try_as_reflect()
+                .is_some_and(|reflect| reflect.as_any().type_id() == type_id);
+
+            if !is_concrete {
+                // Entity references may only be collected from concrete values
+                let Some(concrete) = type_registry
+                    .get_type_data::<ReflectFromReflect>(type_id)
+                    .and_then(|from_reflect| from_reflect.from_reflect(component.as_partial_reflect()))
+                else {
+                    warn!(
+                        "{} on entity {} cannot be checked for entity references and will be kept as is",
+                        component.reflect_type_path(),
+                        scene_entity.entity
+                    );
+                    return true;
+                };
+                *component = concrete.into_partial_reflect();
+            }
+
+            let reflect = component
+                .try_as_reflect_mut()
+                .expect("component must be concrete");
+
+            let mut collector = EntityCollector(Vec::new());
+            reflect_component.map_entities(reflect, &mut collector);
+            if !collector.0.iter().any(|entity| is_excluded(*entity)) {
+                return true;
+            }
+
+            let is_relationship = world
+                .components()
+                .get_id(type_id)
+                .and_then(|id| world.components().get_info(id))
+                .is_some_and(|info| info.relationship_accessor().is_some());
+
+            if !is_relationship {
+                warn!(
+                    "{} on entity {} references an excluded entity and will be ignored",
+                    component.reflect_type_path(),
+                    scene_entity.entity
+                );
+            }
+
+            false
+        });
+    }
+}
+
+struct EntityCollector(Vec<Entity>);
+
+impl EntityMapper for EntityCollector {
+    fn get_mapped(&mut self, source: Entity) -> Entity {
+        self.0.push(source);
+        source
+    }
+
+    fn set_mapped(&mut self, _source: Entity, _target: Entity) {}
 }
 
 // TODO: Documentation
@@ -459,6 +1425,7 @@ mod tests {
     use bevy_ecs::system::RunSystemOnce;
 
     use super::*;
+    use crate::{MapContext, RegisterSaveMapping};
 
     #[derive(Component, Default, Reflect)]
     #[reflect(Component)]
@@ -567,6 +1534,423 @@ mod tests {
         remove_file(PATH).unwrap();
     }
 
+    #[test]
+    fn test_dont_save() {
+        pub const PATH: &str = "test_dont_save.ron";
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component)]
+        struct Excluded;
+
+        let mut app = app();
+        app.register_type::<Excluded>()
+            .add_observer(save_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.spawn((Foo, Excluded, DontSave::<Excluded>::default()));
+            commands.spawn((Foo, Excluded));
+            commands.trigger_save(SaveWorld::default_into_file(PATH));
+        });
+
+        let data = read_to_string(PATH).unwrap();
+        assert_eq!(data.matches("Foo").count(), 2);
+        assert_eq!(data.matches("Excluded").count(), 1);
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_register_transient() {
+        pub const PATH: &str = "test_register_transient.ron";
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component)]
+        struct Transient;
+
+        #[derive(Resource, Default, Reflect)]
+        #[reflect(Resource)]
+        struct TransientResource;
+
+        let mut app = app();
+        app.register_type::<Transient>()
+            .register_type::<TransientResource>()
+            .init_resource::<TransientResource>()
+            .register_transient::<Transient>()
+            .register_transient_resource::<TransientResource>()
+            .add_observer(save_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.spawn((Foo, Transient));
+            commands.trigger_save(
+                SaveWorld::default_into_file(PATH).include_resource::<TransientResource>(),
+            );
+        });
+
+        let data = read_to_string(PATH).unwrap();
+        assert!(data.contains("Foo"));
+        assert!(!data.contains("Transient"));
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_prune_required_defaults() {
+        pub const PATH: &str = "test_prune_required_defaults.ron";
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component)]
+        #[require(Save, Requirement, Health = Health(100))]
+        struct Requirer;
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component, Default, @SavePrune)]
+        struct Requirement(u32);
+
+        #[derive(Component, Default, Reflect)]
+        #[reflect(Component, Default)]
+        struct Health(u32);
+
+        let mut app = app();
+        app.register_type::<Requirer>()
+            .register_type::<Requirement>()
+            .register_type::<Health>()
+            .add_observer(save_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.spawn(Requirer);
+            commands.spawn((Requirer, Requirement(1), Health(0)));
+            commands.spawn((Save, Requirement(0)));
+            commands.trigger_save(SaveWorld::default_into_file(PATH).prune_required_defaults());
+        });
+
+        let data = read_to_string(PATH).unwrap();
+        assert_eq!(data.matches("Requirer").count(), 2);
+        assert_eq!(data.matches("Requirement").count(), 2);
+        assert!(data.contains("Requirement\": (1)"));
+
+        // Requirements with custom constructors are never pruned
+        assert_eq!(data.matches("Health").count(), 2);
+        assert!(data.contains("Health\": (0)"));
+        assert!(data.contains("Health\": (100)"));
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_map_component_preserves_target() {
+        pub const PATH: &str = "test_map_component_preserves_target.ron";
+
+        #[derive(Component)]
+        struct Source(u32);
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Target(u32);
+
+        let mut app = app();
+        app.register_type::<Target>()
+            .add_observer(save_on_default_event);
+
+        let entity = app.world_mut().spawn((Foo, Source(1), Target(7))).id();
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(
+                SaveWorld::default_into_file(PATH)
+                    .map_component(|Source(value): &Source| Target(*value)),
+            );
+        });
+
+        let data = read_to_string(PATH).unwrap();
+        assert!(data.contains("Target\": (1)"));
+
+        let world = app.world();
+        assert_eq!(world.get::<Source>(entity).unwrap().0, 1);
+        assert_eq!(world.get::<Target>(entity).unwrap().0, 7);
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_map_component_with_context() {
+        pub const PATH: &str = "test_map_component_with_context.ron";
+
+        #[derive(Component)]
+        struct Target(Entity);
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct TargetName(String);
+
+        let mut app = app();
+        app.register_type::<TargetName>()
+            .add_observer(save_on_default_event);
+
+        let target = app.world_mut().spawn(Name::new("Target")).id();
+        app.world_mut().spawn((Foo, Target(target)));
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(
+                SaveWorld::default_into_file(PATH).map_component_with_context(
+                    |Target(target): &Target, context: MapContext| {
+                        TargetName(context.world.get::<Name>(*target).unwrap().to_string())
+                    },
+                ),
+            );
+        });
+
+        let data = read_to_string(PATH).unwrap();
+        assert!(data.contains("TargetName\": (\"Target\")"));
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_try_map_component() {
+        pub const PATH: &str = "test_try_map_component.ron";
+
+        #[derive(Component)]
+        struct Source(Option<u32>);
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Target(u32);
+
+        #[derive(Debug, Error)]
+        #[error("invalid source")]
+        struct InvalidSource;
+
+        let mut app = app();
+        app.register_type::<Target>();
+
+        let a = app.world_mut().spawn((Foo, Source(Some(1)))).id();
+        let b = app.world_mut().spawn((Foo, Source(None))).id();
+
+        let saved = SaveCommand(
+            SaveWorld::default_into_file(PATH)
+                .try_map_component(|Source(value): &Source| value.map(Target)),
+        )
+        .apply(app.world_mut());
+        assert!(saved.is_ok());
+
+        let data = read_to_string(PATH).unwrap();
+        assert_eq!(data.matches("Target").count(), 1);
+        assert!(!app.world().entity(a).contains::<Target>());
+        assert!(!app.world().entity(b).contains::<Target>());
+
+        let saved = SaveCommand(
+            SaveWorld::default_into_file(PATH)
+                .try_map_component(|Source(value): &Source| value.map(Target).ok_or(InvalidSource)),
+        )
+        .apply(app.world_mut());
+        assert!(matches!(saved, Err(SaveError::Map(error)) if error.is::<InvalidSource>()));
+        assert!(!app.world().entity(a).contains::<Target>());
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_map_component_in_scene() {
+        pub const PATH: &str = "test_map_component_in_scene.ron";
+
+        #[derive(Component)]
+        struct Unreflected(u32);
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        #[component(on_insert = Self::on_insert)]
+        struct Reflected(u32);
+
+        impl Reflected {
+            fn on_insert(_world: DeferredWorld, _ctx: HookContext) {
+                panic!("mapped output must not be inserted");
+            }
+        }
+
+        let mut app = app();
+        app.register_type::<Reflected>()
+            .add_observer(save_on_default_event);
+
+        let entity = app.world_mut().spawn((Foo, Unreflected(12))).id();
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(
+                SaveWorld::default_into_file(PATH)
+                    .map_component_in_scene(|Unreflected(value): &Unreflected| Reflected(*value)),
+            );
+        });
+
+        let data = read_to_string(PATH).unwrap();
+        assert!(data.contains("Reflected\": (12)"));
+
+        let world = app.world();
+        assert_eq!(world.get::<Unreflected>(entity).unwrap().0, 12);
+        assert!(!world.entity(entity).contains::<Reflected>());
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_map_component_in_scene_replaces_target() {
+        pub const PATH: &str = "test_map_component_in_scene_replaces_target.ron";
+
+        #[derive(Component)]
+        struct Legacy(u32);
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Current(u32);
+
+        let mut app = app();
+        app.register_type::<Current>()
+            .add_observer(save_on_default_event);
+
+        let entity = app.world_mut().spawn((Foo, Legacy(12), Current(5))).id();
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(
+                SaveWorld::default_into_file(PATH)
+                    .map_component_in_scene(|Legacy(value): &Legacy| Current(*value)),
+            );
+        });
+
+        let data = read_to_string(PATH).unwrap();
+        assert!(data.contains("Current\": (12)"));
+        assert!(!data.contains("Current\": (5)"));
+
+        let world = app.world();
+        assert_eq!(world.get::<Current>(entity).unwrap().0, 5);
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_save_readonly() {
+        pub const PATH: &str = "test_save_readonly.ron";
+
+        let mut app = app();
+        app.world_mut().spawn(Foo);
+
+        let saved = app
+            .world_mut()
+            .run_system_once(|world: &World| {
+                save_readonly(SaveWorld::default_into_file(PATH), world)
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.entities().count(), 1);
+
+        let data = read_to_string(PATH).unwrap();
+        assert!(data.contains("Foo"));
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_save_readonly_with_mappers() {
+        pub const PATH: &str = "test_save_readonly_with_mappers.ron";
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Mapped;
+
+        let mut app = app();
+        app.world_mut().spawn(Foo);
+
+        let result = app
+            .world_mut()
+            .run_system_once(|world: &World| {
+                save_readonly(
+                    SaveWorld::default_into_file(PATH).map_component(|_: &Foo| Mapped),
+                    world,
+                )
+            })
+            .unwrap();
+        assert!(matches!(result, Err(SaveError::ReadOnly(_))));
+
+        app.register_save_mapping(|_: &Foo| Mapped, |_: &Mapped| Foo);
+        let result = app
+            .world_mut()
+            .run_system_once(|world: &World| {
+                save_readonly(SaveWorld::default_into_file(PATH), world)
+            })
+            .unwrap();
+        assert!(matches!(result, Err(SaveError::ReadOnly(_))));
+
+        assert!(!exists(PATH).unwrap());
+    }
+
+    #[test]
+    fn test_save_subtree() {
+        use crate::load::{load_on_default_event, LoadWorld, TriggerLoad};
+
+        pub const PATH: &str = "test_save_subtree.ron";
+
+        let mut app = app();
+        app.add_observer(save_on::<SaveWorld<()>>);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            let parent = commands.spawn(Foo).id();
+            let root = commands
+                .spawn((Foo, ChildOf(parent)))
+                .with_children(|root| {
+                    root.spawn(Foo);
+                    root.spawn(Foo);
+                })
+                .id();
+            commands.spawn(Foo);
+            commands.trigger_save(SaveWorld::subtree_into_file([root], PATH));
+        });
+
+        let mut app = self::app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(LoadWorld::additive_from_file(PATH));
+        });
+
+        let world = app.world_mut();
+        assert_eq!(world.query::<&Foo>().iter(world).count(), 3);
+        let (root, children) = world
+            .query_filtered::<(Entity, &Children), Without<ChildOf>>()
+            .single(world)
+            .unwrap();
+        assert_eq!(children.len(), 2);
+        for child in children.iter() {
+            assert_eq!(world.get::<ChildOf>(child).unwrap().parent(), root);
+        }
+
+        remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn test_remove_dynamic_references() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Target(#[entities] Entity);
+
+        let mut app = app();
+        app.register_type::<Target>();
+
+        let world = app.world_mut();
+        let outside = world.spawn_empty().id();
+        let entity = world.spawn(Target(outside)).id();
+
+        let mut scene = DynamicScene {
+            resources: Vec::new(),
+            entities: vec![DynamicEntity {
+                entity,
+                components: vec![Target(outside).to_dynamic()],
+            }],
+        };
+
+        remove_references(world, &mut scene, |entity| entity == outside);
+        assert!(scene.entities[0].components.is_empty());
+
+        // Partial values cannot be checked, but must not be dropped
+        let mut partial = bevy_reflect::DynamicTupleStruct::default();
+        partial.set_represented_type(Some(<Target as bevy_reflect::Typed>::type_info()));
+        scene.entities[0].components.push(Box::new(partial));
+
+        remove_references(world, &mut scene, |entity| entity == outside);
+        assert_eq!(scene.entities[0].components.len(), 1);
+    }
+
     #[test]
     fn test_map_component() {
         pub const PATH: &str = "test_map_component.ron";
@@ -0,0 +1,109 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use moonshine_save::prelude::*;
+
+const SAVE_PATH: &str = "test_bundle_mapper.ron";
+const SAVE_PATH_EXISTING: &str = "test_bundle_mapper_existing.ron";
+
+#[derive(Component, Clone)]
+#[require(Save)]
+struct Position(u32); // Not serializable
+
+#[derive(Component, Clone)]
+struct Velocity(u32); // Not serializable
+
+#[derive(Component, Clone, Reflect, Debug, PartialEq)]
+#[reflect(Component)]
+struct PhysicsSave {
+    position: u32,
+    velocity: u32,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.register_type::<PhysicsSave>()
+        .add_plugins(MinimalPlugins);
+    app
+}
+
+#[test]
+fn main() {
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        let entity = app.world_mut().spawn((Position(1), Velocity(2))).id();
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(SaveWorld::default_into_file(SAVE_PATH).map_bundle(
+                |(position, velocity): &(Position, Velocity)| PhysicsSave {
+                    position: position.0,
+                    velocity: velocity.0,
+                },
+            ));
+        });
+
+        let world = app.world();
+        assert_eq!(world.get::<Position>(entity).unwrap().0, 1);
+        assert_eq!(world.get::<Velocity>(entity).unwrap().0, 2);
+        assert!(!world.entity(entity).contains::<PhysicsSave>());
+
+        let data = fs::read_to_string(SAVE_PATH).unwrap();
+        assert!(data.contains("PhysicsSave"));
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(LoadWorld::default_from_file(SAVE_PATH).map_bundle(
+                |(physics,): &(PhysicsSave,)| {
+                    (Position(physics.position), Velocity(physics.velocity))
+                },
+            ));
+        });
+
+        let world = app.world_mut();
+        let (position, velocity) = world
+            .query::<(&Position, &Velocity)>()
+            .single(world)
+            .unwrap();
+        assert_eq!(position.0, 1);
+        assert_eq!(velocity.0, 2);
+        assert_eq!(world.query::<&PhysicsSave>().iter(world).count(), 0);
+    }
+
+    fs::remove_file(SAVE_PATH).unwrap();
+}
+
+#[test]
+fn existing_output() {
+    let mut app = app();
+    app.add_observer(save_on_default_event);
+
+    let existing = PhysicsSave {
+        position: 3,
+        velocity: 4,
+    };
+    let entity = app
+        .world_mut()
+        .spawn((Position(1), Velocity(2), existing.clone()))
+        .id();
+    let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+        commands.trigger_save(SaveWorld::default_into_file(SAVE_PATH_EXISTING).map_bundle(
+            |(position, velocity): &(Position, Velocity)| PhysicsSave {
+                position: position.0,
+                velocity: velocity.0,
+            },
+        ));
+    });
+
+    let world = app.world();
+    assert_eq!(world.get::<PhysicsSave>(entity), Some(&existing));
+    assert_eq!(world.get::<Position>(entity).unwrap().0, 1);
+    assert_eq!(world.get::<Velocity>(entity).unwrap().0, 2);
+
+    fs::remove_file(SAVE_PATH_EXISTING).unwrap();
+}
@@ -0,0 +1,89 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use moonshine_save::prelude::*;
+
+const PROFILE_PATH: &str = "test_domain_profile.ron";
+const LEVEL_PATH: &str = "test_domain_level.ron";
+
+struct Profile;
+
+struct Level;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+#[require(Save<Profile>)]
+struct Player(u32);
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+#[require(Save<Level>)]
+struct Enemy(u32);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.register_type::<Player>()
+        .register_type::<Enemy>()
+        .add_plugins(MinimalPlugins)
+        .add_observer(save_on::<SaveWorld<DefaultSaveFilter<Profile>>>)
+        .add_observer(save_on::<SaveWorld<DefaultSaveFilter<Level>>>)
+        .add_observer(load_on::<LoadWorld<DefaultUnloadFilter<Level>>>);
+    app
+}
+
+#[test]
+fn main() {
+    {
+        let mut app = app();
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.spawn(Player(1));
+            commands.spawn(Enemy(1));
+            commands.spawn(Enemy(2));
+
+            commands.trigger_save(SaveWorld::domain_into_file::<Profile>(PROFILE_PATH));
+            commands.trigger_save(SaveWorld::domain_into_file::<Level>(LEVEL_PATH));
+        });
+
+        let profile = fs::read_to_string(PROFILE_PATH).unwrap();
+        assert!(profile.contains("Player"));
+        assert!(!profile.contains("Enemy"));
+
+        let level = fs::read_to_string(LEVEL_PATH).unwrap();
+        assert!(!level.contains("Player"));
+        assert!(level.contains("Enemy"));
+    }
+
+    {
+        let mut app = app();
+
+        let (player, enemy) = app
+            .world_mut()
+            .run_system_once(|mut commands: Commands| {
+                let player = commands.spawn(Player(2)).id();
+                let enemy = commands.spawn(Enemy(3)).id();
+
+                commands.trigger_load(LoadWorld::domain_from_file::<Level>(LEVEL_PATH));
+
+                (player, enemy)
+            })
+            .unwrap();
+
+        let world = app.world_mut();
+        assert_eq!(world.get::<Player>(player).unwrap().0, 2);
+        assert!(world.get_entity(enemy).is_err());
+        assert_eq!(world.query::<&Enemy>().iter(world).count(), 2);
+        for entity in world
+            .query_filtered::<Entity, With<Enemy>>()
+            .iter(world)
+            .collect::<Vec<_>>()
+        {
+            assert!(world.entity(entity).contains::<Save<Level>>());
+            assert!(!world.entity(entity).contains::<Save>());
+        }
+    }
+
+    fs::remove_file(PROFILE_PATH).unwrap();
+    fs::remove_file(LEVEL_PATH).unwrap();
+}
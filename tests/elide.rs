@@ -0,0 +1,180 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use moonshine_save::prelude::*;
+
+const SAVE_PATH: &str = "test_elide.ron";
+const SAVE_PATH_FILTERED: &str = "test_elide_filtered.ron";
+
+#[derive(Component, Reflect, Debug, PartialEq)]
+#[reflect(Component, Default)]
+#[require(Save)]
+struct Stats {
+    health: u32,
+    mana: u32,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            health: 100,
+            mana: 0,
+        }
+    }
+}
+
+// `ReflectDefault` is registered manually, so `FromReflect` cannot fill in elided fields by itself
+#[derive(Component, Reflect, Debug, PartialEq)]
+#[reflect(Component)]
+#[require(Save)]
+struct Follow {
+    #[entities]
+    target: Entity,
+    distance: u32,
+}
+
+impl Default for Follow {
+    fn default() -> Self {
+        Self {
+            target: Entity::PLACEHOLDER,
+            distance: 10,
+        }
+    }
+}
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource, Default)]
+struct Settings {
+    volume: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { volume: 1.0 }
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.register_type::<Stats>()
+        .register_type::<Follow>()
+        .register_type_data::<Follow, ReflectDefault>()
+        .register_type::<Settings>()
+        .init_resource::<Settings>()
+        .add_plugins(MinimalPlugins);
+    app
+}
+
+#[test]
+fn main() {
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.spawn(Stats {
+                health: 100,
+                mana: 5,
+            });
+            commands.spawn(Stats::default());
+            commands.trigger_save(
+                SaveWorld::default_into_file(SAVE_PATH)
+                    .include_resource::<Settings>()
+                    .elide_defaults(),
+            );
+        });
+
+        let data = fs::read_to_string(SAVE_PATH).unwrap();
+        assert_eq!(data.matches("Stats").count(), 2);
+        assert!(data.contains("mana: 5"));
+        assert!(!data.contains("health"));
+        assert!(!data.contains("volume"));
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        app.world_mut().resource_mut::<Settings>().volume = 0.5;
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(LoadWorld::default_from_file(SAVE_PATH));
+        });
+
+        let world = app.world_mut();
+        assert_eq!(world.resource::<Settings>().volume, 1.0);
+
+        let mut stats: Vec<_> = world.query::<&Stats>().iter(world).collect();
+        stats.sort_by_key(|stats| stats.mana);
+        assert_eq!(
+            stats,
+            [
+                &Stats::default(),
+                &Stats {
+                    health: 100,
+                    mana: 5
+                }
+            ]
+        );
+    }
+
+    fs::remove_file(SAVE_PATH).unwrap();
+}
+
+#[test]
+fn filtered() {
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            let target = commands.spawn_empty().insert(Save).id();
+            commands.spawn(Stats {
+                health: 100,
+                mana: 5,
+            });
+            commands.spawn((
+                Stats::default(),
+                Follow {
+                    target,
+                    distance: 10,
+                },
+            ));
+            commands
+                .trigger_save(SaveWorld::default_into_file(SAVE_PATH_FILTERED).elide_defaults());
+        });
+
+        let data = fs::read_to_string(SAVE_PATH_FILTERED).unwrap();
+        assert!(!data.contains("distance"));
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(
+                LoadWorld::default_from_file(SAVE_PATH_FILTERED).include_entities_with::<Stats>(),
+            );
+        });
+
+        let world = app.world_mut();
+        assert_eq!(world.query::<&Follow>().iter(world).count(), 0);
+
+        let mut stats: Vec<_> = world.query::<&Stats>().iter(world).collect();
+        stats.sort_by_key(|stats| stats.mana);
+        assert_eq!(
+            stats,
+            [
+                &Stats::default(),
+                &Stats {
+                    health: 100,
+                    mana: 5
+                }
+            ]
+        );
+    }
+
+    fs::remove_file(SAVE_PATH_FILTERED).unwrap();
+}
@@ -0,0 +1,61 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use moonshine_save::prelude::*;
+
+const SAVE_PATH: &str = "test_resource_mapper.ron";
+
+#[derive(Resource)]
+struct Rng(Box<u64>); // Not serializable
+
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+struct RngState(u64);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.register_type::<RngState>().add_plugins(MinimalPlugins);
+    app
+}
+
+#[test]
+fn main() {
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        app.insert_resource(Rng(Box::new(42)));
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(
+                SaveWorld::default_into_file(SAVE_PATH)
+                    .map_resource(|Rng(seed): &Rng| RngState(**seed)),
+            );
+        });
+
+        let world = app.world();
+        assert_eq!(*world.resource::<Rng>().0, 42);
+        assert!(!world.contains_resource::<RngState>());
+
+        let data = fs::read_to_string(SAVE_PATH).unwrap();
+        assert!(data.contains("RngState"));
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(
+                LoadWorld::default_from_file(SAVE_PATH)
+                    .map_resource(|&RngState(seed): &RngState| Rng(Box::new(seed))),
+            );
+        });
+
+        let world = app.world();
+        assert_eq!(*world.resource::<Rng>().0, 42);
+        assert!(!world.contains_resource::<RngState>());
+    }
+
+    fs::remove_file(SAVE_PATH).unwrap();
+}
@@ -0,0 +1,77 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use moonshine_save::prelude::*;
+
+const SAVE_PATH: &str = "test_save_hooks.ron";
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, SaveHooks)]
+#[require(Save)]
+struct Counter {
+    saved: u32,
+    #[reflect(ignore)]
+    pending: u32, // Flushed before save
+    #[reflect(ignore)]
+    doubled: u32, // Derived after load
+}
+
+impl SaveHooks for Counter {
+    fn prepare_for_save(entity: &mut EntityWorldMut) {
+        let mut counter = entity.get_mut::<Counter>().unwrap();
+        counter.saved += counter.pending;
+        counter.pending = 0;
+    }
+
+    fn after_load(entity: &mut EntityWorldMut) {
+        let mut counter = entity.get_mut::<Counter>().unwrap();
+        counter.doubled = counter.saved * 2;
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.register_type::<Counter>().add_plugins(MinimalPlugins);
+    app
+}
+
+#[test]
+fn main() {
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        let entity = app
+            .world_mut()
+            .spawn(Counter {
+                saved: 1,
+                pending: 2,
+                ..default()
+            })
+            .id();
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(SaveWorld::default_into_file(SAVE_PATH));
+        });
+
+        let counter = app.world().get::<Counter>(entity).unwrap();
+        assert_eq!(counter.saved, 3);
+        assert_eq!(counter.pending, 0);
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(LoadWorld::default_from_file(SAVE_PATH));
+        });
+
+        let world = app.world_mut();
+        let counter = world.query::<&Counter>().single(world).unwrap();
+        assert_eq!(counter.saved, 3);
+        assert_eq!(counter.doubled, 6);
+    }
+
+    fs::remove_file(SAVE_PATH).unwrap();
+}
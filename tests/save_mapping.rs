@@ -0,0 +1,100 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use moonshine_save::prelude::*;
+
+const SAVE_PATH: &str = "test_save_mapping.ron";
+const SAVE_PATH_CHAINED: &str = "test_save_mapping_chained.ron";
+
+#[derive(Component)]
+#[require(Save)]
+struct Health(Box<u32>); // Not serializable
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SavedHealth(u32);
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(Save)]
+struct LegacyHealth(u32); // Saved by an older version
+
+fn app() -> App {
+    let mut app = App::new();
+    app.register_type::<SavedHealth>()
+        .register_type::<LegacyHealth>()
+        .register_save_mapping(
+            |Health(value): &Health| SavedHealth(**value),
+            |&SavedHealth(value): &SavedHealth| Health(Box::new(value)),
+        )
+        .add_plugins(MinimalPlugins);
+    app
+}
+
+#[test]
+fn main() {
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        let entity = app.world_mut().spawn(Health(Box::new(42))).id();
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(SaveWorld::default_into_file(SAVE_PATH));
+        });
+
+        let world = app.world();
+        assert_eq!(*world.get::<Health>(entity).unwrap().0, 42);
+        assert!(!world.entity(entity).contains::<SavedHealth>());
+
+        let data = fs::read_to_string(SAVE_PATH).unwrap();
+        assert!(data.contains("SavedHealth"));
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(LoadWorld::default_from_file(SAVE_PATH));
+        });
+
+        let world = app.world_mut();
+        assert_eq!(*world.query::<&Health>().single(world).unwrap().0, 42);
+        assert_eq!(world.query::<&SavedHealth>().iter(world).count(), 0);
+    }
+
+    fs::remove_file(SAVE_PATH).unwrap();
+}
+
+#[test]
+fn chained() {
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.spawn(LegacyHealth(42));
+            commands.trigger_save(SaveWorld::default_into_file(SAVE_PATH_CHAINED));
+        });
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(
+                LoadWorld::default_from_file(SAVE_PATH_CHAINED)
+                    .map_component(|&LegacyHealth(value): &LegacyHealth| SavedHealth(value)),
+            );
+        });
+
+        let world = app.world_mut();
+        assert_eq!(*world.query::<&Health>().single(world).unwrap().0, 42);
+        assert_eq!(world.query::<&SavedHealth>().iter(world).count(), 0);
+        assert_eq!(world.query::<&LegacyHealth>().iter(world).count(), 0);
+    }
+
+    fs::remove_file(SAVE_PATH_CHAINED).unwrap();
+}
@@ -0,0 +1,117 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy_ecs::system::RunSystemOnce;
+use moonshine_save::prelude::*;
+use moonshine_save::SerdeComponents;
+use serde::{Deserialize, Serialize};
+
+const SAVE_PATH: &str = "test_serde_component.ron";
+const SAVE_PATH_UNREGISTERED: &str = "test_serde_component_unregistered.ron";
+
+#[derive(Component, TypePath, Serialize, Deserialize, PartialEq, Debug)]
+#[require(Save)]
+struct Inventory(Vec<String>); // Not reflected
+
+#[derive(Component, TypePath, Serialize, Deserialize, PartialEq, Debug)]
+struct Gold(u32); // Not reflected
+
+fn app() -> App {
+    let mut app = App::new();
+    app.register_serde_component::<Inventory>()
+        .register_serde_component::<Gold>()
+        .add_plugins(MinimalPlugins);
+    app
+}
+
+#[test]
+fn main() {
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        let entity = app
+            .world_mut()
+            .spawn((Inventory(vec!["Sword".into()]), Gold(42)))
+            .id();
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(SaveWorld::default_into_file(SAVE_PATH));
+        });
+
+        let world = app.world();
+        assert!(world.entity(entity).contains::<Inventory>());
+        assert!(!world.entity(entity).contains::<SerdeComponents>());
+
+        let data = fs::read_to_string(SAVE_PATH).unwrap();
+        assert!(data.contains("SerdeComponents"));
+        assert!(data.contains("Sword"));
+        assert!(!data.contains("\\\"Sword\\\""));
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(LoadWorld::default_from_file(SAVE_PATH));
+        });
+
+        let world = app.world_mut();
+        let (inventory, gold) = world.query::<(&Inventory, &Gold)>().single(world).unwrap();
+        assert_eq!(inventory, &Inventory(vec!["Sword".into()]));
+        assert_eq!(gold, &Gold(42));
+        assert_eq!(world.query::<&SerdeComponents>().iter(world).count(), 0);
+    }
+
+    fs::remove_file(SAVE_PATH).unwrap();
+}
+
+#[test]
+fn unregistered() {
+    const UNKNOWN: &str = "serde_component::Unknown";
+
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        let mut existing = SerdeComponents::default();
+        existing
+            .0
+            .insert(UNKNOWN.into(), ron::value::RawValue::from_rust(&7).unwrap());
+        let entity = app
+            .world_mut()
+            .spawn((Inventory(vec!["Shield".into()]), existing))
+            .id();
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_save(SaveWorld::default_into_file(SAVE_PATH_UNREGISTERED));
+        });
+
+        let world = app.world();
+        let SerdeComponents(existing) = world.get::<SerdeComponents>(entity).unwrap();
+        assert_eq!(existing.keys().collect::<Vec<_>>(), [UNKNOWN]);
+
+        let data = fs::read_to_string(SAVE_PATH_UNREGISTERED).unwrap();
+        assert!(data.contains(UNKNOWN));
+        assert!(data.contains("Shield"));
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(LoadWorld::default_from_file(SAVE_PATH_UNREGISTERED));
+        });
+
+        let world = app.world_mut();
+        let (inventory, SerdeComponents(unregistered)) = world
+            .query::<(&Inventory, &SerdeComponents)>()
+            .single(world)
+            .unwrap();
+        assert_eq!(inventory, &Inventory(vec!["Shield".into()]));
+        assert_eq!(unregistered.keys().collect::<Vec<_>>(), [UNKNOWN]);
+    }
+
+    fs::remove_file(SAVE_PATH_UNREGISTERED).unwrap();
+}
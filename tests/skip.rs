@@ -0,0 +1,70 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use moonshine_save::prelude::*;
+
+const SAVE_PATH: &str = "test_skip.ron";
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+#[require(Save)]
+struct Unit {
+    health: u32,
+    #[reflect(@SaveSkip)]
+    path: Vec<u32>,
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+#[require(Save)]
+struct Tag(u32, #[reflect(@SaveSkip)] u32, u32);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.register_type::<Unit>()
+        .register_type::<Tag>()
+        .add_plugins(MinimalPlugins);
+    app
+}
+
+#[test]
+fn main() {
+    {
+        let mut app = app();
+        app.add_observer(save_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.spawn(Unit {
+                health: 42,
+                path: vec![1, 2, 3],
+            });
+            commands.spawn(Tag(1, 2, 3));
+            commands.trigger_save(SaveWorld::default_into_file(SAVE_PATH));
+        });
+
+        let data = fs::read_to_string(SAVE_PATH).unwrap();
+        assert!(data.contains("health"));
+        assert!(!data.contains("path"));
+    }
+
+    {
+        let mut app = app();
+        app.add_observer(load_on_default_event);
+
+        let _ = app.world_mut().run_system_once(|mut commands: Commands| {
+            commands.trigger_load(LoadWorld::default_from_file(SAVE_PATH));
+        });
+
+        let world = app.world_mut();
+        let unit = world.query::<&Unit>().single(world).unwrap();
+        assert_eq!(unit.health, 42);
+        assert!(unit.path.is_empty());
+
+        // Fields of tuple structs are never skipped
+        let tag = world.query::<&Tag>().single(world).unwrap();
+        assert_eq!((tag.0, tag.1, tag.2), (1, 2, 3));
+    }
+
+    fs::remove_file(SAVE_PATH).unwrap();
+}